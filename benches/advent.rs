@@ -1,8 +1,9 @@
-use std::fs::read_to_string;
-
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
-use advent2021::get_day;
+use advent2021::config::Config;
+use advent2021::normalize::normalize;
+use advent2021::params::{parse_override, Params};
+use advent2021::{get_both, get_day, get_parameterized, read_input};
 
 fn medwards_solvers_benchmark(c: &mut Criterion) {
     create_solvers_benchmark(c, None);
@@ -25,8 +26,35 @@ fn create_solvers_benchmark(c: &mut Criterion, namespace: Option<&str>) {
             Err(_) => return,
         };
 
-        let contents = read_to_string(input_path)
+        let contents = read_input(input_path)
             .unwrap_or_else(|e| panic!("Unable to read from {} - {}", input_path, e));
+        let contents = normalize(contents.as_str(), false).unwrap().into_owned();
+
+        if let Some((parameters, part_one, part_two)) = get_parameterized(day) {
+            // overrides of parameters other days declare are skipped
+            let overrides = overrides();
+            let overrides: Vec<_> = overrides
+                .iter()
+                .filter(|(name, _)| parameters.iter().any(|p| p.name == name))
+                .map(|(name, value)| (name.as_str(), *value))
+                .collect();
+            if !overrides.is_empty() {
+                let label: Vec<_> = overrides
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", name, value))
+                    .collect();
+                [(1, "One", part_one), (2, "Two", part_two)]
+                    .iter()
+                    .for_each(|(part, name, solver)| {
+                        let params = Params::new(parameters, *part, overrides.as_slice()).unwrap();
+                        let id = format!("Day {}, Part {} ({})", day, name, label.join(", "));
+                        c.bench_function(id.as_str(), |b| {
+                            b.iter(|| solver(black_box(contents.as_str()), &params).unwrap())
+                        });
+                    });
+                return;
+            }
+        }
 
         c.bench_function(format!("Day {}, Part One", day).as_str(), |b| {
             b.iter(|| part_one(black_box(contents.as_str())).unwrap())
@@ -35,12 +63,56 @@ fn create_solvers_benchmark(c: &mut Criterion, namespace: Option<&str>) {
         c.bench_function(format!("Day {}, Part Two", day).as_str(), |b| {
             b.iter(|| part_two(black_box(contents.as_str())).unwrap())
         });
+
+        if let Some(both) = get_both(day) {
+            c.bench_function(format!("Day {}, Both Parts", day).as_str(), |b| {
+                b.iter(|| both(black_box(contents.as_str())).unwrap())
+            });
+        }
+    });
+}
+
+// Solvers in other languages from the `[external]` table of `advent.toml`, ie `"alice::6" = "day6"`
+fn external_solvers_benchmark(c: &mut Criterion) {
+    let config = Config::discover(None).unwrap_or_else(|e| panic!("{}", e));
+    config.externals().for_each(|day| {
+        let (day, input_path, part_one, part_two) = config
+            .get_day(day)
+            .unwrap_or_else(|e| panic!("external solver '{}' - {}", day, e));
+
+        let contents = read_input(&input_path)
+            .unwrap_or_else(|e| panic!("Unable to read from {} - {}", input_path.display(), e));
+        let contents = normalize(contents.as_str(), config.strict_input())
+            .unwrap()
+            .into_owned();
+
+        [("One", part_one), ("Two", part_two)]
+            .iter()
+            .for_each(|(name, solver)| {
+                c.bench_function(format!("Day {}, Part {}", day, name).as_str(), |b| {
+                    b.iter(|| solver(black_box(contents.as_str())).unwrap())
+                });
+            });
     });
 }
 
+// Parameter overrides like `ADVENT_PARAMS=days=18,steps=5`
+fn overrides() -> Vec<(String, usize)> {
+    std::env::var("ADVENT_PARAMS")
+        .unwrap_or_default()
+        .split(',')
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            let (name, value) = parse_override(s).expect("ADVENT_PARAMS must be name=value,...");
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
 criterion_group!(
     benches,
     medwards_solvers_benchmark,
-    // jhorstmann_solvers_benchmark -- Day 6, Part One has a bug
+    jhorstmann_solvers_benchmark,
+    external_solvers_benchmark
 );
 criterion_main!(benches);
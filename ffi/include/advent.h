@@ -0,0 +1,42 @@
+/* Generated by advent2021-ffi, regenerate with: UPDATE_HEADER=1 cargo test -p advent2021-ffi */
+#ifndef ADVENT_H
+#define ADVENT_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* the answer was written to out_buf */
+#define ADVENT_OK 0
+/* a required pointer was NULL */
+#define ADVENT_ERR_INVALID_ARGUMENT 1
+/* day is not listed by advent_solvers */
+#define ADVENT_ERR_INVALID_DAY 2
+/* part is neither 1 nor 2 */
+#define ADVENT_ERR_INVALID_PART 3
+/* day or the input is not valid UTF-8 */
+#define ADVENT_ERR_INVALID_UTF8 4
+/* the solver rejected the input, the error message was written to out_buf */
+#define ADVENT_ERR_SOLVER 5
+/* out_buf can't hold the result, *out_len was set to the required length */
+#define ADVENT_ERR_BUFFER_TOO_SMALL 6
+/* the solver panicked (usually on malformed input) */
+#define ADVENT_ERR_PANIC 7
+
+/* Writes up to capacity solver identifiers (static strings) into out and returns the total
+ * number of solvers. advent_solvers(NULL, 0) returns the count only. */
+size_t advent_solvers(const char **out, size_t capacity);
+
+/* Solves part (1 or 2) of day for the UTF-8 input. *out_len is the capacity of out_buf on
+ * entry and the length of the NUL terminated answer (or solver error message) on return. */
+int advent_solve(const char *day, uint32_t part, const uint8_t *input_ptr, size_t input_len,
+                 char *out_buf, size_t *out_len);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ADVENT_H */
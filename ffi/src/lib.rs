@@ -0,0 +1,240 @@
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_int};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::OnceLock;
+use std::{ptr, slice, str};
+
+pub const ADVENT_OK: c_int = 0;
+pub const ADVENT_ERR_INVALID_ARGUMENT: c_int = 1;
+pub const ADVENT_ERR_INVALID_DAY: c_int = 2;
+pub const ADVENT_ERR_INVALID_PART: c_int = 3;
+pub const ADVENT_ERR_INVALID_UTF8: c_int = 4;
+pub const ADVENT_ERR_SOLVER: c_int = 5;
+pub const ADVENT_ERR_BUFFER_TOO_SMALL: c_int = 6;
+pub const ADVENT_ERR_PANIC: c_int = 7;
+
+// Source for both the constants in the header and their documentation
+const STATUS_CODES: &[(&str, c_int, &str)] = &[
+    ("ADVENT_OK", ADVENT_OK, "the answer was written to out_buf"),
+    (
+        "ADVENT_ERR_INVALID_ARGUMENT",
+        ADVENT_ERR_INVALID_ARGUMENT,
+        "a required pointer was NULL",
+    ),
+    (
+        "ADVENT_ERR_INVALID_DAY",
+        ADVENT_ERR_INVALID_DAY,
+        "day is not listed by advent_solvers",
+    ),
+    (
+        "ADVENT_ERR_INVALID_PART",
+        ADVENT_ERR_INVALID_PART,
+        "part is neither 1 nor 2",
+    ),
+    (
+        "ADVENT_ERR_INVALID_UTF8",
+        ADVENT_ERR_INVALID_UTF8,
+        "day or the input is not valid UTF-8",
+    ),
+    (
+        "ADVENT_ERR_SOLVER",
+        ADVENT_ERR_SOLVER,
+        "the solver rejected the input, the error message was written to out_buf",
+    ),
+    (
+        "ADVENT_ERR_BUFFER_TOO_SMALL",
+        ADVENT_ERR_BUFFER_TOO_SMALL,
+        "out_buf can't hold the result, *out_len was set to the required length",
+    ),
+    (
+        "ADVENT_ERR_PANIC",
+        ADVENT_ERR_PANIC,
+        "the solver panicked (usually on malformed input)",
+    ),
+];
+
+/// Lists the identifiers accepted by `advent_solve`.
+///
+/// Writes up to `capacity` pointers to static NUL terminated strings into `out` and returns the
+/// total number of solvers, so `advent_solvers(NULL, 0)` can be used to size `out`.
+///
+/// # Safety
+///
+/// `out` must be valid for `capacity` writes.
+#[no_mangle]
+pub unsafe extern "C" fn advent_solvers(out: *mut *const c_char, capacity: usize) -> usize {
+    let names = solver_names();
+    if !out.is_null() {
+        names
+            .iter()
+            .take(capacity)
+            .enumerate()
+            .for_each(|(i, name)| *out.add(i) = name.as_ptr());
+    }
+    names.len()
+}
+
+/// Solves `part` (1 or 2) of `day` for the UTF-8 input in `input_ptr`.
+///
+/// `*out_len` is the capacity of `out_buf` on entry. On `ADVENT_OK` the answer, and on
+/// `ADVENT_ERR_SOLVER` the error message, is written to `out_buf` as a NUL terminated string and
+/// `*out_len` is set to its length excluding the terminator.
+///
+/// # Safety
+///
+/// `day` must be a NUL terminated string, `input_ptr` must be valid for `input_len` reads and
+/// `out_buf` for `*out_len` writes.
+#[no_mangle]
+pub unsafe extern "C" fn advent_solve(
+    day: *const c_char,
+    part: u32,
+    input_ptr: *const u8,
+    input_len: usize,
+    out_buf: *mut c_char,
+    out_len: *mut usize,
+) -> c_int {
+    if day.is_null() || (input_ptr.is_null() && input_len > 0) || out_len.is_null() {
+        return ADVENT_ERR_INVALID_ARGUMENT;
+    }
+    if out_buf.is_null() && *out_len > 0 {
+        return ADVENT_ERR_INVALID_ARGUMENT;
+    }
+
+    let day = match CStr::from_ptr(day).to_str() {
+        Ok(day) => day,
+        Err(_) => return ADVENT_ERR_INVALID_UTF8,
+    };
+    let input = if input_len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(input_ptr, input_len)
+    };
+    let input = match str::from_utf8(input) {
+        Ok(input) => input,
+        Err(_) => return ADVENT_ERR_INVALID_UTF8,
+    };
+
+    let (status, message) = match solve(day, part, input) {
+        Ok(answer) => (ADVENT_OK, answer.to_string()),
+        Err((status, message)) => (status, message),
+    };
+    if status != ADVENT_OK && status != ADVENT_ERR_SOLVER {
+        return status;
+    }
+
+    let capacity = *out_len;
+    *out_len = message.len();
+    if message.len() >= capacity {
+        return ADVENT_ERR_BUFFER_TOO_SMALL;
+    }
+    ptr::copy_nonoverlapping(message.as_ptr(), out_buf as *mut u8, message.len());
+    *out_buf.add(message.len()) = 0;
+    status
+}
+
+fn solve(day: &str, part: u32, input: &str) -> Result<u64, (c_int, String)> {
+    let (_, _, part_one, part_two) =
+        advent2021::get_day(day).map_err(|_| (ADVENT_ERR_INVALID_DAY, String::new()))?;
+    let solver = match part {
+        1 => part_one,
+        2 => part_two,
+        _ => return Err((ADVENT_ERR_INVALID_PART, String::new())),
+    };
+    let input = advent2021::normalize::normalize(input, false)
+        .map_err(|e| (ADVENT_ERR_SOLVER, e.to_string()))?;
+    match catch_unwind(AssertUnwindSafe(|| solver(input.as_ref()))) {
+        Ok(Ok(answer)) => Ok(answer),
+        Ok(Err(e)) => Err((ADVENT_ERR_SOLVER, e.to_string())),
+        Err(_) => Err((ADVENT_ERR_PANIC, String::new())),
+    }
+}
+
+fn solver_names() -> &'static [CString] {
+    static NAMES: OnceLock<Vec<CString>> = OnceLock::new();
+    NAMES.get_or_init(|| {
+        advent2021::DAYS
+            .iter()
+            .map(|day| CString::new(*day).expect("day identifiers contain no NUL"))
+            .collect()
+    })
+}
+
+/// Renders `include/advent.h`
+pub fn header() -> String {
+    let mut header = String::from(
+        "/* Generated by advent2021-ffi, regenerate with: UPDATE_HEADER=1 cargo test -p advent2021-ffi */\n\
+         #ifndef ADVENT_H\n\
+         #define ADVENT_H\n\
+         \n\
+         #include <stddef.h>\n\
+         #include <stdint.h>\n\
+         \n\
+         #ifdef __cplusplus\n\
+         extern \"C\" {\n\
+         #endif\n\
+         \n",
+    );
+    STATUS_CODES.iter().for_each(|(name, value, doc)| {
+        header.push_str(format!("/* {} */\n#define {} {}\n", doc, name, value).as_str());
+    });
+    header.push_str(
+        "\n\
+         /* Writes up to capacity solver identifiers (static strings) into out and returns the total\n \
+         * number of solvers. advent_solvers(NULL, 0) returns the count only. */\n\
+         size_t advent_solvers(const char **out, size_t capacity);\n\
+         \n\
+         /* Solves part (1 or 2) of day for the UTF-8 input. *out_len is the capacity of out_buf on\n \
+         * entry and the length of the NUL terminated answer (or solver error message) on return. */\n\
+         int advent_solve(const char *day, uint32_t part, const uint8_t *input_ptr, size_t input_len,\n                 \
+         char *out_buf, size_t *out_len);\n\
+         \n\
+         #ifdef __cplusplus\n\
+         }\n\
+         #endif\n\
+         \n\
+         #endif /* ADVENT_H */\n",
+    );
+    header
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use std::fs::{read_to_string, write};
+
+    use super::*;
+
+    const HEADER_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/include/advent.h");
+
+    #[test]
+    fn test_header_is_current() {
+        if env::var_os("UPDATE_HEADER").is_some() {
+            write(HEADER_PATH, header()).unwrap();
+        }
+        assert_eq!(
+            header(),
+            read_to_string(HEADER_PATH).unwrap(),
+            "include/advent.h is stale, regenerate it with UPDATE_HEADER=1 cargo test -p advent2021-ffi"
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(
+            Ok(7),
+            solve("1", 1, "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n")
+        );
+        assert_eq!(
+            Err((ADVENT_ERR_INVALID_DAY, String::new())),
+            solve("25", 1, "")
+        );
+        assert_eq!(
+            Err((ADVENT_ERR_INVALID_PART, String::new())),
+            solve("1", 3, "")
+        );
+        assert_eq!(Ok(5934), solve("6", 1, "\u{feff}3,4,3,1,2\r\n"));
+        assert_eq!(ADVENT_ERR_SOLVER, solve("1", 1, "one").unwrap_err().0);
+        assert_eq!(ADVENT_ERR_SOLVER, solve("6", 1, "9").unwrap_err().0);
+        assert_eq!(ADVENT_ERR_PANIC, solve("3", 2, "").unwrap_err().0);
+    }
+}
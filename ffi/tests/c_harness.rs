@@ -0,0 +1,44 @@
+#![cfg(unix)]
+
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// Integration tests run from target/<profile>/deps, next to the freshly built cdylib
+fn library_dir() -> PathBuf {
+    let exe = env::current_exe().expect("test executable path");
+    exe.parent()
+        .expect("test executable directory")
+        .to_path_buf()
+}
+
+#[test]
+fn test_c_harness() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let library_dir = library_dir();
+    let harness = library_dir.join("advent_c_harness");
+
+    let status = Command::new(env::var("CC").unwrap_or_else(|_| "cc".to_string()))
+        .arg(manifest_dir.join("tests/harness.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg("-L")
+        .arg(&library_dir)
+        .arg("-ladvent")
+        .arg("-o")
+        .arg(&harness)
+        .status()
+        .expect("unable to run the C compiler");
+    assert!(status.success(), "C harness failed to compile");
+
+    let output = Command::new(&harness)
+        .env("LD_LIBRARY_PATH", &library_dir)
+        .env("DYLD_LIBRARY_PATH", &library_dir)
+        .output()
+        .expect("unable to run the C harness");
+    assert!(
+        output.status.success(),
+        "C harness failed:\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
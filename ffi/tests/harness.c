@@ -0,0 +1,65 @@
+#include <stdio.h>
+#include <string.h>
+
+#include "advent.h"
+
+static int failures = 0;
+
+#define CHECK(condition)                                                       \
+    do {                                                                       \
+        if (!(condition)) {                                                    \
+            fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__,  \
+                    #condition);                                               \
+            failures++;                                                        \
+        }                                                                      \
+    } while (0)
+
+static const char EXAMPLE[] = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n";
+
+int main(void) {
+    char out[64];
+    size_t out_len;
+
+    /* listing */
+    size_t count = advent_solvers(NULL, 0);
+    CHECK(count > 0);
+    const char *names[64];
+    CHECK(advent_solvers(names, 64) == count);
+    CHECK(strcmp(names[0], "1") == 0);
+
+    /* both parts of the day 1 example */
+    out_len = sizeof(out);
+    CHECK(advent_solve("1", 1, (const uint8_t *)EXAMPLE, strlen(EXAMPLE), out, &out_len) == ADVENT_OK);
+    CHECK(strcmp(out, "7") == 0);
+    CHECK(out_len == 1);
+
+    out_len = sizeof(out);
+    CHECK(advent_solve("one", 2, (const uint8_t *)EXAMPLE, strlen(EXAMPLE), out, &out_len) == ADVENT_OK);
+    CHECK(strcmp(out, "5") == 0);
+
+    /* error codes */
+    out_len = sizeof(out);
+    CHECK(advent_solve("26", 1, (const uint8_t *)EXAMPLE, strlen(EXAMPLE), out, &out_len) == ADVENT_ERR_INVALID_DAY);
+
+    out_len = sizeof(out);
+    CHECK(advent_solve("1", 3, (const uint8_t *)EXAMPLE, strlen(EXAMPLE), out, &out_len) == ADVENT_ERR_INVALID_PART);
+
+    out_len = sizeof(out);
+    CHECK(advent_solve("1", 1, (const uint8_t *)"x\n", 2, out, &out_len) == ADVENT_ERR_SOLVER);
+    CHECK(out_len > 0 && strlen(out) == out_len);
+
+    out_len = sizeof(out);
+    CHECK(advent_solve("1", 1, (const uint8_t *)"\xff", 1, out, &out_len) == ADVENT_ERR_INVALID_UTF8);
+
+    out_len = sizeof(out);
+    CHECK(advent_solve(NULL, 1, (const uint8_t *)EXAMPLE, strlen(EXAMPLE), out, &out_len) == ADVENT_ERR_INVALID_ARGUMENT);
+
+    out_len = 1;
+    CHECK(advent_solve("1", 1, (const uint8_t *)EXAMPLE, strlen(EXAMPLE), out, &out_len) == ADVENT_ERR_BUFFER_TOO_SMALL);
+    CHECK(out_len == 1);
+
+    if (failures == 0) {
+        printf("ok\n");
+    }
+    return failures == 0 ? 0 : 1;
+}
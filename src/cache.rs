@@ -0,0 +1,180 @@
+use std::collections::hash_map::DefaultHasher;
+use std::ffi::OsStr;
+use std::fs::{self, create_dir_all, read_to_string, write};
+use std::hash::{Hash, Hasher};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::output::Outcome;
+
+/// Answers of earlier runs, one file per answer named after the hash of everything it depends on:
+/// the day and part, the parameter overrides, the input and the build of the runner
+pub struct Cache {
+    directory: PathBuf,
+    build: u64,
+}
+
+impl Cache {
+    /// Caches in `directory` for the running build, identified by the size and modification time
+    /// of the executable so that any rebuild starts afresh
+    pub fn open(directory: PathBuf) -> io::Result<Cache> {
+        let metadata = std::env::current_exe()?.metadata()?;
+        let modified = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .map_err(io::Error::other)?;
+        let mut hasher = DefaultHasher::new();
+        (metadata.len(), modified).hash(&mut hasher);
+        Ok(Cache::new(directory, hasher.finish()))
+    }
+
+    fn new(directory: PathBuf, build: u64) -> Cache {
+        Cache { directory, build }
+    }
+
+    pub fn key(&self, day: &str, part: usize, overrides: &[(&str, usize)], contents: &str) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        (self.build, day, part, overrides, contents).hash(&mut hasher);
+        hasher.finish()
+    }
+
+    pub fn get(&self, key: u64) -> Option<u64> {
+        read_to_string(self.path(key)).ok()?.trim().parse().ok()
+    }
+
+    pub fn put(&self, key: u64, answer: u64) -> io::Result<()> {
+        create_dir_all(&self.directory)?;
+        write(self.path(key), format!("{}\n", answer))
+    }
+
+    /// The cached answer for `key`, or the outcome of `solve` whose answer is cached for next time
+    pub fn get_or_solve(&self, key: u64, solve: impl FnOnce() -> Outcome) -> Outcome {
+        if let Some(answer) = self.get(key) {
+            log::debug!(target: "cache", "{:016x} is cached", key);
+            return Outcome::Answer(answer);
+        }
+        let outcome = solve();
+        self.put_outcome(key, &outcome);
+        outcome
+    }
+
+    /// The cached answers of both parts, or the outcomes of `solve` solving both when either
+    /// isn't cached
+    pub fn get_or_solve_both(
+        &self,
+        keys: [u64; 2],
+        solve: impl FnOnce() -> [Outcome; 2],
+    ) -> [Outcome; 2] {
+        if let (Some(one), Some(two)) = (self.get(keys[0]), self.get(keys[1])) {
+            log::debug!(target: "cache", "{:016x} and {:016x} are cached", keys[0], keys[1]);
+            return [Outcome::Answer(one), Outcome::Answer(two)];
+        }
+        let outcomes = solve();
+        keys.iter()
+            .zip(outcomes.iter())
+            .for_each(|(key, outcome)| self.put_outcome(*key, outcome));
+        outcomes
+    }
+
+    fn put_outcome(&self, key: u64, outcome: &Outcome) {
+        if let Outcome::Answer(answer) = outcome {
+            if let Err(e) = self.put(key, *answer) {
+                log::warn!(target: "cache", "Unable to cache {:016x} - {}", key, e);
+            }
+        }
+    }
+
+    fn path(&self, key: u64) -> PathBuf {
+        self.directory.join(format!("{:016x}", key))
+    }
+}
+
+/// Removes every cached answer, returning how many there were. Only files named like answers are
+/// removed, so a cache configured to a shared directory leaves the other files alone, and the
+/// directory itself only goes once it is empty.
+pub fn clear(directory: &Path) -> io::Result<usize> {
+    let entries = match fs::read_dir(directory) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+    let mut answers = 0;
+    for entry in entries {
+        let entry = entry?;
+        if is_answer(&entry.file_name()) && entry.file_type()?.is_file() {
+            fs::remove_file(entry.path())?;
+            answers += 1;
+        }
+    }
+    if fs::read_dir(directory)?.next().is_none() {
+        fs::remove_dir(directory)?;
+    }
+    Ok(answers)
+}
+
+// Answers are named after their key, see `Cache::path`
+fn is_answer(name: &OsStr) -> bool {
+    name.to_str()
+        .is_some_and(|name| name.len() == 16 && name.bytes().all(|b| b.is_ascii_hexdigit()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+
+    use super::*;
+
+    #[test]
+    fn test_cache() {
+        let directory = temp_dir().join(format!("advent2021-{}-test-cache", std::process::id()));
+        let cache = Cache::new(directory.clone(), 1);
+        let key = cache.key("6", 1, &[], "3,4,3,1,2");
+
+        assert_eq!(None, cache.get(key));
+        cache.put(key, 5934).unwrap();
+        assert_eq!(Some(5934), cache.get(key));
+
+        assert_ne!(key, cache.key("6", 2, &[], "3,4,3,1,2"));
+        assert_ne!(key, cache.key("6", 1, &[("days", 18)], "3,4,3,1,2"));
+        assert_ne!(key, cache.key("6", 1, &[], "3,4,3,1,3"));
+        assert_ne!(
+            key,
+            Cache::new(directory.clone(), 2).key("6", 1, &[], "3,4,3,1,2")
+        );
+
+        let keys = [key, cache.key("6", 2, &[], "3,4,3,1,2")];
+        // part one alone being cached solves both again
+        let outcomes = cache.get_or_solve_both(keys, || {
+            [
+                Outcome::Answer(5934),
+                Outcome::Failed("no fish".to_string()),
+            ]
+        });
+        assert!(matches!(
+            outcomes,
+            [Outcome::Answer(5934), Outcome::Failed(_)]
+        ));
+        assert_eq!(None, cache.get(keys[1]));
+        cache.put(keys[1], 26984457539).unwrap();
+        let outcomes = cache.get_or_solve_both(keys, || unreachable!("both are cached"));
+        assert!(matches!(
+            outcomes,
+            [Outcome::Answer(5934), Outcome::Answer(26984457539)]
+        ));
+
+        assert_eq!(2, clear(&directory).unwrap());
+        assert_eq!(None, cache.get(key));
+        assert!(!directory.exists());
+        assert_eq!(0, clear(&directory).unwrap());
+
+        // other files sharing the directory are kept, and so is the directory
+        cache.put(key, 5934).unwrap();
+        write(directory.join("notes.txt"), "").unwrap();
+        write(directory.join("0123456789abcdeg"), "").unwrap();
+        assert_eq!(1, clear(&directory).unwrap());
+        assert!(directory.join("notes.txt").exists());
+        assert!(directory.join("0123456789abcdeg").exists());
+        fs::remove_dir_all(&directory).unwrap();
+    }
+}
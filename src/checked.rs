@@ -0,0 +1,41 @@
+use core::fmt;
+
+/// Arithmetic of a solver leaving the range of its type, returned instead of wrapping or panicking
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Overflow;
+
+impl fmt::Display for Overflow {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "arithmetic overflow")
+    }
+}
+
+impl core::error::Error for Overflow {}
+
+/// Sums answers, or fails on the first partial sum that doesn't fit
+pub fn sum(values: impl IntoIterator<Item = u64>) -> Result<u64, Overflow> {
+    values
+        .into_iter()
+        .try_fold(0u64, |sum, value| sum.checked_add(value).ok_or(Overflow))
+}
+
+/// Multiplies answers, or fails on the first partial product that doesn't fit
+pub fn product(values: impl IntoIterator<Item = u64>) -> Result<u64, Overflow> {
+    values.into_iter().try_fold(1u64, |product, value| {
+        product.checked_mul(value).ok_or(Overflow)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sum_and_product() {
+        assert_eq!(Ok(6), sum([1, 2, 3]));
+        assert_eq!(Err(Overflow), sum([u64::MAX, 1]));
+        assert_eq!(Ok(24), product([2, 3, 4]));
+        assert_eq!(Ok(1), product([]));
+        assert_eq!(Err(Overflow), product([u64::MAX / 2, 3]));
+    }
+}
@@ -0,0 +1,87 @@
+use std::io::Write;
+
+use clap_generate::generators::{Bash, Fish, Zsh};
+
+pub const SHELLS: &[&str] = &["bash", "zsh", "fish"];
+
+const BIN_NAME: &str = env!("CARGO_BIN_NAME");
+
+/// Writes the completion script of `shell` for the subcommands and arguments of `app`
+pub fn generate(shell: &str, app: &mut clap::App, out: &mut dyn Write) {
+    match shell {
+        "bash" => clap_generate::generate(Bash, app, BIN_NAME, out),
+        "zsh" => clap_generate::generate(Zsh, app, BIN_NAME, out),
+        "fish" => {
+            clap_generate::generate(Fish, app, BIN_NAME, out);
+            fish_values(app, out);
+        }
+        _ => unreachable!("clap only accepts SHELLS"),
+    }
+}
+
+// The fish generator only completes options, the possible values of positional arguments like DAY
+// are completed after their subcommand
+fn fish_values(app: &clap::App, out: &mut dyn Write) {
+    app.get_subcommands().for_each(|subcommand| {
+        let values: Vec<_> = subcommand
+            .get_positionals()
+            .filter_map(clap::Arg::get_possible_values)
+            .flatten()
+            .map(clap::ArgValue::get_name)
+            .collect();
+        if !values.is_empty() {
+            writeln!(
+                out,
+                "complete -c {} -n \"__fish_seen_subcommand_from {}\" -a \"{}\"",
+                BIN_NAME,
+                subcommand.get_name(),
+                values.join(" ")
+            )
+            .expect("Failed to write to generated file");
+        }
+    });
+}
+
+// completes the days of every solver
+#[cfg(all(test, feature = "all-days"))]
+mod tests {
+    use super::*;
+
+    fn script(shell: &str) -> String {
+        let mut out = Vec::new();
+        generate(shell, &mut crate::app_with_days(advent2021::DAYS), &mut out);
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_bash() {
+        let script = script("bash");
+        assert!(
+            script.contains("opts=\"-h -v --help --config --year --strict-input --log day list ")
+        );
+        assert!(script.contains("opts=\"-h --help clear help\""));
+        assert!(script.contains(" 6 jhorstmann::6 7 "));
+        assert!(script.ends_with("complete -F _advent2021 -o bashdefault -o default advent2021\n"));
+    }
+
+    #[test]
+    fn test_zsh() {
+        let script = script("zsh");
+        assert!(script.starts_with("#compdef advent2021\n"));
+        assert!(script.contains("'--port=[Port to listen on]"));
+        assert!(script.contains("'--year=[Year of the days given without one"));
+        assert!(script.contains("'*::DAY:(1 2 3 4 5 6 jhorstmann::6 7 "));
+    }
+
+    #[test]
+    fn test_fish() {
+        let script = script("fish");
+        assert!(script.contains(
+            "complete -c advent2021 -n \"__fish_use_subcommand\" -f -a \"day\" -d 'Select day to solve'\n"
+        ));
+        assert!(script.contains("complete -c advent2021 -n \"__fish_use_subcommand\" -l log"));
+        assert!(script.contains(
+            "complete -c advent2021 -n \"__fish_seen_subcommand_from day\" -a \"1 2 3 4 5 6 jhorstmann::6 7 "
+        ));
+    }
+}
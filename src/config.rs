@@ -0,0 +1,382 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs::read_to_string;
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+/// Looked up in the working directory when `--config` isn't given
+pub const FILE_NAME: &str = "advent.toml";
+
+use crate::external::External;
+use crate::DEFAULT_YEAR;
+
+/// A solver of one part, whether built in or external
+pub type PartSolver = Arc<dyn Fn(&str) -> Result<u64> + Send + Sync>;
+
+type Day = (String, PathBuf, PartSolver, PartSolver);
+
+/// Settings of the runner, see `advent.toml` in the README for the file format
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    // Relative paths are resolved against the directory of the config file
+    inputs: Option<PathBuf>,
+    pub year: Option<u16>,
+    namespace: Option<String>,
+    pub format: Format,
+    timeout: Option<u64>,
+    session: Option<PathBuf>,
+    color: Color,
+    pub normalize: Normalize,
+    cache: Option<PathBuf>,
+    history: Option<PathBuf>,
+    /// JSON of the private leaderboard, fetched with the session token
+    leaderboard: Option<String>,
+    /// Command lines of solvers in other languages by namespaced day, ie `alice::6`
+    external: BTreeMap<String, String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Format {
+    #[default]
+    Text,
+    Json,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Color {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+/// Whether inputs are normalised before solving or only checked, see `crate::normalize`
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Normalize {
+    #[default]
+    Fix,
+    Strict,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(anyhow!("unknown format '{}'", s)),
+        }
+    }
+}
+
+impl Config {
+    /// Loads `path`, or `advent.toml` if present, falling back to the defaults
+    pub fn discover(path: Option<&str>) -> Result<Config> {
+        match path {
+            Some(path) => Config::load(Path::new(path)),
+            None if Path::new(FILE_NAME).is_file() => Config::load(Path::new(FILE_NAME)),
+            None => Ok(Config::default()),
+        }
+    }
+
+    fn load(path: &Path) -> Result<Config> {
+        let contents = read_to_string(path)
+            .map_err(|e| anyhow!("Unable to read from {} - {}", path.display(), e))?;
+        let mut config: Config = toml::from_str(contents.as_str())
+            .map_err(|e| anyhow!("Invalid config {} - {}", path.display(), e))?;
+
+        let base = path.parent().unwrap_or_else(|| Path::new(""));
+        config.inputs = config.inputs.map(|inputs| base.join(inputs));
+        config.session = config.session.map(|session| base.join(session));
+        config.cache = config.cache.map(|cache| base.join(cache));
+        config.history = config.history.map(|history| base.join(history));
+        Ok(config)
+    }
+
+    /// Looks up a day in the configured year unless it names one (ie `2021:5`), preferring the
+    /// configured namespace, ie `6` is `jhorstmann::6` when the namespace is `jhorstmann`. External
+    /// solvers are looked up before those of the library. The input path is moved under the
+    /// configured inputs directory and days outside `DEFAULT_YEAR` are identified with their year.
+    pub fn get_day(&self, day: &str) -> Result<Day> {
+        let (year, unqualified) = crate::split_year(day);
+        let year = year.or(self.year).unwrap_or(DEFAULT_YEAR);
+        let namespaced = self
+            .namespace
+            .as_ref()
+            .filter(|_| !unqualified.contains("::"))
+            .map(|namespace| format!("{}::{}", namespace, unqualified));
+        if let Some(day) = namespaced
+            .iter()
+            .map(String::as_str)
+            .chain(std::iter::once(unqualified))
+            .find_map(|name| self.get_external(year, name))
+        {
+            return day;
+        }
+        let (name, input_path, part_one, part_two) = namespaced
+            .and_then(|namespaced| crate::get_year_day(year, namespaced.as_str()).ok())
+            .map_or_else(|| crate::get_year_day(year, unqualified), Ok)
+            .map_err(|e| match e {
+                crate::Error::InvalidYear => anyhow!("{} has no solvers", year),
+                crate::Error::InvalidDay => anyhow!("'{}' is not a known day", day),
+            })?;
+
+        let name = if year == DEFAULT_YEAR {
+            name.to_string()
+        } else {
+            format!("{}:{}", year, name)
+        };
+        Ok((
+            name,
+            self.input_path(input_path),
+            Arc::new(part_one),
+            Arc::new(part_two),
+        ))
+    }
+
+    fn get_external(&self, year: u16, day: &str) -> Option<Result<Day>> {
+        let name = if year == DEFAULT_YEAR {
+            day.to_string()
+        } else {
+            format!("{}:{}", year, day)
+        };
+        let command = self.external.get(&name)?;
+        let number = day
+            .split_once("::")
+            .and_then(|(_, number)| number.parse::<u8>().ok());
+        let number = match number {
+            Some(number) => number,
+            None => {
+                return Some(Err(anyhow!(
+                    "external solver '{}' must be named namespace::DAY",
+                    name
+                )))
+            }
+        };
+        let external = match External::new(command) {
+            Ok(external) => Arc::new(external),
+            Err(e) => return Some(Err(anyhow!("external solver '{}' - {}", name, e))),
+        };
+        let input_path = self.input_path(format!("inputs/{}/day/{}/input", year, number).as_str());
+        let part_two = external.clone();
+        Some(Ok((
+            name,
+            input_path,
+            Arc::new(move |contents: &str| external.solve(1, contents)),
+            Arc::new(move |contents: &str| part_two.solve(2, contents)),
+        )))
+    }
+
+    /// Whether a day returned by `get_day` is solved by an external command, whose answers can
+    /// change without the runner being rebuilt
+    pub fn is_external(&self, day: &str) -> bool {
+        self.external.contains_key(day)
+    }
+
+    /// Names of the external solvers of `DEFAULT_YEAR`, ie `alice::6`
+    pub fn externals(&self) -> impl Iterator<Item = &str> {
+        self.external
+            .keys()
+            .map(String::as_str)
+            .filter(|name| crate::split_year(name).0.is_none())
+    }
+
+    /// Moves an `INPUT_PATH` under the configured inputs directory
+    pub fn input_path(&self, input_path: &str) -> PathBuf {
+        match (&self.inputs, input_path.strip_prefix("inputs/")) {
+            (Some(inputs), Some(relative)) => inputs.join(relative),
+            _ => PathBuf::from(input_path),
+        }
+    }
+
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout.map(Duration::from_secs)
+    }
+
+    /// Directory of the answer cache, `.advent-cache` in the working directory by default
+    pub fn cache(&self) -> PathBuf {
+        self.cache
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(".advent-cache"))
+    }
+
+    /// File of the run history, `.advent-history` in the working directory by default
+    pub fn history(&self) -> PathBuf {
+        self.history
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(".advent-history"))
+    }
+
+    pub fn leaderboard(&self) -> Option<&str> {
+        self.leaderboard.as_deref()
+    }
+
+    pub fn session(&self) -> Option<&Path> {
+        self.session.as_deref()
+    }
+
+    pub fn strict_input(&self) -> bool {
+        self.normalize == Normalize::Strict
+    }
+
+    pub fn colored(&self) -> bool {
+        match self.color {
+            Color::Auto => io::stdout().is_terminal(),
+            Color::Always => true,
+            Color::Never => false,
+        }
+    }
+}
+
+impl fmt::Display for Config {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let unset = || "(unset)".to_string();
+        writeln!(
+            f,
+            "inputs = {}",
+            self.inputs
+                .as_ref()
+                .map_or_else(|| "inputs".to_string(), |p| p.display().to_string())
+        )?;
+        writeln!(f, "year = {}", self.year.unwrap_or(DEFAULT_YEAR))?;
+        writeln!(
+            f,
+            "namespace = {}",
+            self.namespace.clone().unwrap_or_else(unset)
+        )?;
+        writeln!(f, "format = {}", self.format)?;
+        writeln!(
+            f,
+            "timeout = {}",
+            self.timeout.map_or_else(unset, |t| format!("{}s", t))
+        )?;
+        writeln!(
+            f,
+            "session = {}",
+            self.session
+                .as_ref()
+                .map_or_else(unset, |p| p.display().to_string())
+        )?;
+        writeln!(f, "color = {}", self.color)?;
+        writeln!(f, "normalize = {}", self.normalize)?;
+        writeln!(f, "cache = {}", self.cache().display())?;
+        writeln!(f, "history = {}", self.history().display())?;
+        writeln!(
+            f,
+            "leaderboard = {}",
+            self.leaderboard.clone().unwrap_or_else(unset)
+        )?;
+        self.external
+            .iter()
+            .try_for_each(|(name, command)| writeln!(f, "external.{} = {}", name, command))
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Json => write!(f, "json"),
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Color::Auto => write!(f, "auto"),
+            Color::Always => write!(f, "always"),
+            Color::Never => write!(f, "never"),
+        }
+    }
+}
+
+impl fmt::Display for Normalize {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Normalize::Fix => write!(f, "fix"),
+            Normalize::Strict => write!(f, "strict"),
+        }
+    }
+}
+
+// every test looks up day 6
+#[cfg(all(test, feature = "day6"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(Config::default(), config);
+        let (name, input_path, _, _) = config.get_day("6").unwrap();
+        assert_eq!("6", name);
+        assert_eq!(PathBuf::from("inputs/2021/day/6/input"), input_path);
+        assert!(toml::from_str::<Config>("colour = \"never\"").is_err());
+        assert_eq!(
+            "2020 has no solvers",
+            config.get_day("2020:5").err().unwrap().to_string()
+        );
+    }
+
+    #[cfg(feature = "all-days")]
+    mod all_days {
+        use super::*;
+
+        #[test]
+        fn test_parse() {
+            let config: Config = toml::from_str(
+                "inputs = \"/srv/advent\"\nnamespace = \"jhorstmann\"\nformat = \"json\"\ntimeout = 5\ncolor = \"never\"\nnormalize = \"strict\"\n",
+            )
+            .unwrap();
+            assert_eq!(Format::Json, config.format);
+            assert_eq!(Some(Duration::from_secs(5)), config.timeout());
+            assert!(!config.colored());
+            assert!(config.strict_input());
+
+            let (name, input_path, _, _) = config.get_day("six").unwrap();
+            assert_eq!("jhorstmann::6", name);
+            assert_eq!(PathBuf::from("/srv/advent/2021/day/6/input"), input_path);
+            let (name, input_path, _, _) = config.get_day("7").unwrap();
+            assert_eq!("7", name);
+            assert_eq!(PathBuf::from("/srv/advent/2021/day/7/input"), input_path);
+            let (name, _, _, _) = config.get_day("2021:jhorstmann::6").unwrap();
+            assert_eq!("jhorstmann::6", name);
+        }
+
+        #[test]
+        fn test_external() {
+            let config: Config = toml::from_str(
+                "namespace = \"alice\"\n[external]\n\"alice::6\" = \"python3 day6.py\"\n\"bob::7\" = \"\"\n\"bob::eight\" = \"day8\"\n",
+            )
+            .unwrap();
+            let (name, input_path, _, _) = config.get_day("6").unwrap();
+            assert_eq!("alice::6", name);
+            assert_eq!(PathBuf::from("inputs/2021/day/6/input"), input_path);
+            let (name, _, _, _) = config.get_day("7").unwrap();
+            assert_eq!("7", name);
+            assert_eq!(
+                "external solver 'bob::7' - empty command",
+                config.get_day("bob::7").err().unwrap().to_string()
+            );
+            assert!(config.get_day("bob::eight").is_err());
+            assert!(config.is_external("alice::6"));
+            assert!(!config.is_external("6"));
+            assert_eq!(
+                vec!["alice::6", "bob::7", "bob::eight"],
+                config.externals().collect::<Vec<_>>()
+            );
+        }
+    }
+}
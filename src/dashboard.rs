@@ -0,0 +1,268 @@
+use std::fs::read_to_string;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use advent2021::config::{Config, PartSolver};
+use advent2021::normalize::normalize;
+
+use crate::history;
+use crate::inputs::answers_path;
+use crate::output::{Outcome, BOLD_GREEN, BOLD_RED, RESET};
+
+/// Clears the line under the cursor
+const CLEAR_LINE: &str = "\x1b[2K";
+
+enum Status {
+    Pending,
+    Running,
+    Done(Outcome, Duration),
+}
+
+struct Row {
+    day: String,
+    part: usize,
+    expected: Option<u64>,
+    status: Status,
+}
+
+/// A table of every part being solved. Live, it is redrawn in place on each change. Otherwise the
+/// header is written first and then each row once it is done, so the output can be piped.
+struct Table<W: Write> {
+    out: W,
+    rows: Vec<Row>,
+    live: bool,
+    colored: bool,
+    drawn: bool,
+}
+
+impl<W: Write> Table<W> {
+    fn set(&mut self, index: usize, status: Status) -> io::Result<()> {
+        let done = matches!(status, Status::Done(..));
+        self.rows[index].status = status;
+        if self.live {
+            self.draw()
+        } else if done {
+            let line = self.line(&self.rows[index]);
+            writeln!(self.out, "{}", line)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn draw(&mut self) -> io::Result<()> {
+        if self.drawn {
+            // back up to the header
+            write!(self.out, "\x1b[{}A", self.rows.len() + 1)?;
+        }
+        self.drawn = true;
+        let lines: Vec<_> = std::iter::once(header())
+            .chain(self.rows.iter().map(|row| self.line(row)))
+            .collect();
+        lines
+            .iter()
+            .try_for_each(|line| writeln!(self.out, "{}{}", CLEAR_LINE, line))?;
+        self.out.flush()
+    }
+
+    fn line(&self, row: &Row) -> String {
+        let (status, color, time, verified, answer) = match &row.status {
+            Status::Pending => ("pending", None, "-".to_string(), "", String::new()),
+            Status::Running => ("running", None, "-".to_string(), "", String::new()),
+            Status::Done(outcome, elapsed) => {
+                let time = format!("{:.2?}", elapsed);
+                match (outcome, row.expected) {
+                    (Outcome::Answer(answer), Some(expected)) if *answer != expected => (
+                        "ok",
+                        Some(BOLD_RED),
+                        time,
+                        "✗",
+                        format!("{}, expected {}", answer, expected),
+                    ),
+                    (Outcome::Answer(answer), expected) => (
+                        "ok",
+                        Some(BOLD_GREEN),
+                        time,
+                        if expected.is_some() { "✓" } else { "" },
+                        answer.to_string(),
+                    ),
+                    (Outcome::Failed(message), _) => {
+                        ("failed", Some(BOLD_RED), time, "", message.clone())
+                    }
+                    (Outcome::Invalid(diagnostic), _) => {
+                        ("failed", Some(BOLD_RED), time, "", diagnostic.to_string())
+                    }
+                    (Outcome::TimedOut(timeout), _) => (
+                        "timeout",
+                        Some(BOLD_RED),
+                        time,
+                        "",
+                        format!("after {}s", timeout.as_secs()),
+                    ),
+                }
+            }
+        };
+        // padded before coloring, escape codes take no room on screen
+        let status = format!("{:<8}", status);
+        let status = match color.filter(|_| self.colored) {
+            Some(color) => format!("{}{}{}", color, status, RESET),
+            None => status,
+        };
+        format!(
+            "{:<16} {:<4} {} {:>10} {:<1} {}",
+            row.day, row.part, status, time, verified, answer
+        )
+        .trim_end()
+        .to_string()
+    }
+}
+
+fn header() -> String {
+    format!(
+        "{:<16} {:<4} {:<8} {:>10} {:<1} {}",
+        "Day", "Part", "Status", "Time", "", "Answer"
+    )
+}
+
+/// Solves both parts of `days` one after the other on a table of their progress, returning
+/// whether every part was answered. The answers are checked against the answers file of each
+/// input and recorded in the run history.
+pub fn run(
+    config: &Config,
+    days: &[(String, PathBuf, PartSolver, PartSolver)],
+    out: impl Write,
+    live: bool,
+) -> io::Result<bool> {
+    let rows = days
+        .iter()
+        .flat_map(|(day, input_path, _, _)| {
+            let expected = read_to_string(answers_path(input_path)).unwrap_or_default();
+            let mut expected: Vec<_> = expected
+                .lines()
+                .map(|answer| answer.trim().parse().ok())
+                .collect();
+            expected.resize(2, None);
+            [1, 2].map(|part| Row {
+                day: day.clone(),
+                part,
+                expected: expected[part - 1],
+                status: Status::Pending,
+            })
+        })
+        .collect();
+    let mut table = Table {
+        out,
+        rows,
+        live,
+        colored: config.colored(),
+        drawn: false,
+    };
+    if live {
+        table.draw()?;
+    } else {
+        writeln!(table.out, "{}", header())?;
+    }
+
+    let history = config.history();
+    let mut answered = true;
+    for (index, (day, input_path, part_one, part_two)) in days.iter().enumerate() {
+        let contents = advent2021::read_input(input_path)
+            .map_err(|e| format!("Unable to read from {} - {}", input_path.display(), e))
+            .and_then(|contents| {
+                normalize(&contents, config.strict_input())
+                    .map(|contents| Arc::<str>::from(contents.as_ref()))
+                    .map_err(|e| format!("{} - {}", input_path.display(), e))
+            });
+        for (part, solver) in [(1, part_one), (2, part_two)] {
+            let row = index * 2 + part - 1;
+            table.set(row, Status::Running)?;
+            let start = Instant::now();
+            let outcome = match &contents {
+                Ok(contents) => crate::solve(solver.clone(), contents.clone(), config.timeout()),
+                Err(e) => Outcome::Failed(e.clone()),
+            };
+            let elapsed = start.elapsed();
+            match outcome {
+                Outcome::Answer(answer) => {
+                    if let Err(e) = history::record(&history, day, part, answer) {
+                        log::warn!(target: "history", "Unable to record an answer - {}", e);
+                    }
+                }
+                _ => answered = false,
+            }
+            table.set(row, Status::Done(outcome, elapsed))?;
+        }
+    }
+    Ok(answered)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(live: bool) -> Table<Vec<u8>> {
+        Table {
+            out: Vec::new(),
+            rows: vec![
+                Row {
+                    day: "6".to_string(),
+                    part: 1,
+                    expected: Some(5934),
+                    status: Status::Pending,
+                },
+                Row {
+                    day: "6".to_string(),
+                    part: 2,
+                    expected: Some(1),
+                    status: Status::Pending,
+                },
+            ],
+            live,
+            colored: false,
+            drawn: false,
+        }
+    }
+
+    fn solve(table: &mut Table<Vec<u8>>) {
+        table.set(0, Status::Running).unwrap();
+        let elapsed = Duration::from_micros(1500);
+        table
+            .set(0, Status::Done(Outcome::Answer(5934), elapsed))
+            .unwrap();
+        table.set(1, Status::Running).unwrap();
+        table
+            .set(1, Status::Done(Outcome::Answer(26984457539), elapsed))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_plain() {
+        let mut table = table(false);
+        solve(&mut table);
+        assert_eq!(
+            "6                1    ok           1.50ms ✓ 5934\n\
+             6                2    ok           1.50ms ✗ 26984457539, expected 1\n",
+            String::from_utf8(table.out).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_live() {
+        let mut table = table(true);
+        table.draw().unwrap();
+        solve(&mut table);
+        let out = String::from_utf8(table.out).unwrap();
+        let frames: Vec<_> = out.split("\x1b[3A").collect();
+        assert_eq!(5, frames.len());
+        assert_eq!(
+            "\x1b[2KDay              Part Status         Time   Answer\n\
+             \x1b[2K6                1    running           -\n\
+             \x1b[2K6                2    pending           -\n",
+            frames[1]
+        );
+        assert!(frames[4].ends_with(
+            "\x1b[2K6                2    ok           1.50ms ✗ 26984457539, expected 1\n"
+        ));
+    }
+}
@@ -1,19 +1,29 @@
-use std::collections::HashSet;
+use alloc::vec::Vec;
 
 use anyhow::Result;
 use arrayvec::ArrayVec;
 
-pub const INPUT_PATH: &str = "inputs/day/8/input";
+use crate::checked;
+use crate::collections::HashSet;
+use crate::metadata::{Metadata, Tag};
 
-pub fn part_one(contents: &str) -> Result<usize> {
-    Ok(count_simple_digits(load_displays(contents)?.as_slice()))
+pub const INPUT_PATH: &str = "inputs/2021/day/8/input";
+
+pub const METADATA: Metadata = Metadata {
+    title: "Seven Segment Search",
+    author: "medwards",
+    tags: &[Tag::Parsing],
+    examples: true,
+};
+
+pub fn part_one(contents: &str) -> Result<u64> {
+    Ok(count_simple_digits(load_displays(contents)?.as_slice()) as u64)
 }
 
-pub fn part_two(contents: &str) -> Result<usize> {
-    Ok(load_displays(contents)?
-        .iter()
-        .map(|(signals, digits)| displayed_value(signals, digits))
-        .sum())
+pub fn part_two(contents: &str) -> Result<u64> {
+    Ok(checked::sum(load_displays(contents)?.iter().map(
+        |(signals, digits)| displayed_value(signals, digits),
+    ))?)
 }
 
 fn count_simple_digits(input: &[([&str; 10], [&str; 4])]) -> usize {
@@ -28,19 +38,19 @@ fn count_simple_digits(input: &[([&str; 10], [&str; 4])]) -> usize {
         .sum()
 }
 
-fn displayed_value(signals: &[&str; 10], digits: &[&str; 4]) -> usize {
+fn displayed_value(signals: &[&str; 10], digits: &[&str; 4]) -> u64 {
     let signals = deduce_digits(signals);
     digits
         .iter()
         .enumerate()
         .map(|(m, digit)| {
-            let magnitude = 10usize.pow(3 - m as u32);
+            let magnitude = 10u64.pow(3 - m as u32);
             let this_signal: HashSet<char> = digit.chars().collect();
             let digit = signals
                 .iter()
                 .enumerate()
                 .find(|(_, signal)| **signal == this_signal)
-                .map(|(i, _)| i)
+                .map(|(i, _)| i as u64)
                 .expect("didn't find matching signal");
             magnitude * digit
         })
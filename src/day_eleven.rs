@@ -1,23 +1,55 @@
+use alloc::{format, string::ToString, vec, vec::Vec};
+
 use anyhow::Result;
 
-pub const INPUT_PATH: &str = "inputs/day/11/input";
+use crate::checked::Overflow;
+use crate::lint::Problem;
+use crate::metadata::{Metadata, Tag};
+use crate::params::{Parameter, Params};
+
+pub const INPUT_PATH: &str = "inputs/2021/day/11/input";
+
+pub const METADATA: Metadata = Metadata {
+    title: "Dumbo Octopus",
+    author: "medwards",
+    tags: &[Tag::Grid, Tag::Simulation],
+    examples: true,
+};
+
+pub const PARAMETERS: &[Parameter] = &[Parameter {
+    name: "steps",
+    about: "steps to count flashes over",
+    part_one: Some(100),
+    part_two: None,
+}];
+
+pub fn part_one(contents: &str) -> Result<u64> {
+    part_one_with(contents, &Params::defaults(PARAMETERS, 1))
+}
+
+pub fn part_two(contents: &str) -> Result<u64> {
+    part_two_with(contents, &Params::defaults(PARAMETERS, 2))
+}
 
-pub fn part_one(contents: &str) -> Result<usize> {
+pub fn part_one_with(contents: &str, params: &Params) -> Result<u64> {
     let grid = load_grid(contents);
-    Ok((0..100)
-        .fold((0, grid), |(flashes, grid), _| {
+    let (flashes, _) =
+        (0..params.get("steps")).try_fold((0u64, grid), |(flashes, grid), step| {
             let (new_flashes, new_grid) = increment(&grid);
-            (flashes + new_flashes, new_grid)
-        })
-        .0)
+            log::trace!(target: "day11", "step {}: {} flashes", step + 1, new_flashes);
+            let flashes = flashes.checked_add(new_flashes as u64).ok_or(Overflow)?;
+            Ok::<_, Overflow>((flashes, new_grid))
+        })?;
+    Ok(flashes)
 }
 
-pub fn part_two(contents: &str) -> Result<usize> {
+pub fn part_two_with(contents: &str, _params: &Params) -> Result<u64> {
     let mut grid = load_grid(contents);
-    let mut iter = 0;
+    let mut iter: u64 = 0;
     loop {
         let (flashes, new_grid) = increment(&grid);
-        iter += 1;
+        iter = iter.checked_add(1).ok_or(Overflow)?;
+        log::trace!(target: "day11", "step {}: {} flashes", iter, flashes);
         if flashes == 100 {
             return Ok(iter);
         }
@@ -25,6 +57,66 @@ pub fn part_two(contents: &str) -> Result<usize> {
     }
 }
 
+/// Both parts from one simulation, running until the flashes are counted and the octopuses
+/// flash together
+pub fn solve_both(contents: &str) -> Result<(u64, u64)> {
+    let steps = Params::defaults(PARAMETERS, 1).get("steps") as u64;
+    let mut grid = load_grid(contents);
+    let (mut counted, mut synchronized) = (0u64, None);
+    let mut step: u64 = 0;
+    while step < steps || synchronized.is_none() {
+        let (flashes, new_grid) = increment(&grid);
+        step = step.checked_add(1).ok_or(Overflow)?;
+        log::trace!(target: "day11", "step {}: {} flashes", step, flashes);
+        if step <= steps {
+            counted = counted.checked_add(flashes as u64).ok_or(Overflow)?;
+        }
+        if flashes == 100 && synchronized.is_none() {
+            synchronized = Some(step);
+        }
+        grid = new_grid;
+    }
+    Ok((
+        counted,
+        synchronized.expect("the loop runs until synchronized"),
+    ))
+}
+
+/// Checks for a 10x10 grid of digits
+pub fn lint(contents: &str) -> Vec<Problem> {
+    let mut problems = Vec::new();
+    let mut rows = 0;
+    contents.lines().enumerate().for_each(|(i, row)| {
+        rows += 1;
+        if i == 10 {
+            problems.push(Problem::new(
+                i + 1,
+                "unexpected row, the grid has 10".to_string(),
+            ));
+        }
+        let digits = row.chars().count();
+        if digits != 10 {
+            problems.push(Problem::new(
+                i + 1,
+                format!("row has {} digits, expected 10", digits),
+            ));
+        }
+        row.chars()
+            .enumerate()
+            .filter(|(_, energy)| !energy.is_ascii_digit())
+            .for_each(|(j, energy)| {
+                problems.push(Problem::new(i + 1, format!("'{}' is not a digit", energy)).at(j + 1))
+            });
+    });
+    if rows < 10 {
+        problems.push(Problem::new(
+            rows.max(1),
+            format!("grid has {} rows, expected 10", rows),
+        ));
+    }
+    problems
+}
+
 fn load_grid(contents: &str) -> [[usize; 10]; 10] {
     let mut grid = [[0; 10]; 10];
     // TODO: just do character - '0'
@@ -189,6 +281,32 @@ fn decrement_octopus(
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_lint() {
+        let grid = "0123456789\n".repeat(10);
+        assert_eq!(Vec::<Problem>::new(), lint(grid.as_str()));
+        let problems: Vec<_> = lint("0123456789\n012345678\n01234x6789")
+            .iter()
+            .map(Problem::to_string)
+            .collect();
+        assert_eq!(
+            vec![
+                "line 2: row has 9 digits, expected 10",
+                "line 3, column 6: 'x' is not a digit",
+                "line 3: grid has 3 rows, expected 10",
+            ],
+            problems
+        );
+        let grid = "0123456789\n".repeat(11);
+        assert_eq!(
+            vec![Problem::new(
+                11,
+                "unexpected row, the grid has 10".to_string()
+            )],
+            lint(grid.as_str())
+        );
+    }
+
     #[test]
     fn test_simple_increment() {
         let mut input = [
@@ -220,6 +338,22 @@ mod tests {
         assert_eq!((9, expected), increment(&mut input));
     }
 
+    #[test]
+    fn test_solve_both() {
+        let contents = "\
+5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
+        assert_eq!((1656, 195), solve_both(contents).unwrap());
+    }
+
     #[test]
     fn test_increment() {
         let mut input = [
@@ -1,14 +1,50 @@
-use anyhow::Result;
+use alloc::{vec, vec::Vec};
 
-pub const INPUT_PATH: &str = "inputs/day/15/input";
+use anyhow::{anyhow, Result};
 
-pub fn part_one(contents: &str) -> Result<usize> {
-    Ok(min_risk(&load_grid(contents)?))
+use crate::metadata::{Metadata, Tag};
+use crate::params::{Parameter, Params};
+
+pub const INPUT_PATH: &str = "inputs/2021/day/15/input";
+
+pub const METADATA: Metadata = Metadata {
+    title: "Chiton",
+    author: "medwards",
+    tags: &[Tag::Grid, Tag::Graph],
+    examples: true,
+};
+
+pub const PARAMETERS: &[Parameter] = &[Parameter {
+    name: "tiles",
+    about: "times the cave is tiled in each direction",
+    part_one: Some(1),
+    part_two: Some(5),
+}];
+
+pub fn part_one(contents: &str) -> Result<u64> {
+    part_one_with(contents, &Params::defaults(PARAMETERS, 1))
+}
+
+pub fn part_two(contents: &str) -> Result<u64> {
+    part_two_with(contents, &Params::defaults(PARAMETERS, 2))
 }
 
-pub fn part_two(contents: &str) -> Result<usize> {
+pub fn part_one_with(contents: &str, params: &Params) -> Result<u64> {
+    tiled_min_risk(contents, params.get("tiles"))
+}
+
+pub fn part_two_with(contents: &str, params: &Params) -> Result<u64> {
+    tiled_min_risk(contents, params.get("tiles"))
+}
+
+fn tiled_min_risk(contents: &str, tiles: usize) -> Result<u64> {
     let grid = load_grid(contents)?;
-    Ok(min_risk(&tile_grid(&grid)))
+    match tiles {
+        0 => Err(anyhow!("the cave must be tiled at least once")),
+        // risks are bounded by 9 per cell of a grid held in memory, so only widened
+        1 => Ok(min_risk(&grid) as u64),
+        _ => Ok(min_risk(&tile_grid(&grid, tiles)) as u64),
+    }
 }
 
 fn min_risk(grid: &Vec<Vec<u8>>) -> usize {
@@ -133,14 +169,14 @@ fn bfs_find_paths(grid: &Vec<Vec<u8>>) -> Vec<usize> {
 }
 */
 
-fn tile_grid(tile: &Vec<Vec<u8>>) -> Vec<Vec<u8>> {
+fn tile_grid(tile: &Vec<Vec<u8>>, tiles: usize) -> Vec<Vec<u8>> {
     let tile_dimensions = (tile.len(), tile.first().expect("empty tile").len());
-    let dimensions = (tile_dimensions.0 * 5, tile_dimensions.1 * 5);
+    let dimensions = (tile_dimensions.0 * tiles, tile_dimensions.1 * tiles);
 
     let mut grid = vec![vec![0; dimensions.1]; dimensions.0];
     (0..dimensions.0).for_each(|i| {
         (0..dimensions.1).for_each(|j| {
-            let tile_index = ((i / tile_dimensions.0) as u8, (j / tile_dimensions.1) as u8);
+            let tile_index = (i / tile_dimensions.0, j / tile_dimensions.1);
             if tile_index == (0, 0) {
                 // copy pasta
                 *grid.get_mut(i).unwrap().get_mut(j).unwrap() =
@@ -148,9 +184,11 @@ fn tile_grid(tile: &Vec<Vec<u8>>) -> Vec<Vec<u8>> {
             } else {
                 let tile_i = i % tile_dimensions.0;
                 let tile_j = j % tile_dimensions.1;
-                let val =
-                    tile.get(tile_i).unwrap().get(tile_j).unwrap() + tile_index.0 + tile_index.1;
-                *grid.get_mut(i).unwrap().get_mut(j).unwrap() = if val > 9 { val - 9 } else { val };
+                let val = *tile.get(tile_i).unwrap().get(tile_j).unwrap() as usize
+                    + tile_index.0
+                    + tile_index.1;
+                // risks past 9 wrap back around to 1
+                *grid.get_mut(i).unwrap().get_mut(j).unwrap() = ((val - 1) % 9 + 1) as u8;
             }
         })
     });
@@ -212,7 +250,7 @@ mod tests {
         let expected =
             load_grid(read_to_string("fixtures/grid_tiled.txt").unwrap().as_str()).unwrap();
 
-        assert_eq!(expected, tile_grid(&input));
+        assert_eq!(expected, tile_grid(&input, 5));
     }
 
     #[test]
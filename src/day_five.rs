@@ -1,19 +1,58 @@
-use std::cmp::max;
-use std::collections::HashMap;
-use std::num::ParseIntError;
+use alloc::{format, string::ToString, vec::Vec};
+use core::cmp::max;
+use core::num::ParseIntError;
 
 use anyhow::Result;
 
-pub const INPUT_PATH: &str = "inputs/day/5/input";
+use crate::collections::HashMap;
+use crate::lint::Problem;
+use crate::metadata::{Metadata, Tag};
 
-pub fn part_one(contents: &str) -> Result<usize> {
+pub const INPUT_PATH: &str = "inputs/2021/day/5/input";
+
+pub const METADATA: Metadata = Metadata {
+    title: "Hydrothermal Venture",
+    author: "medwards",
+    tags: &[Tag::Grid, Tag::Parsing],
+    examples: true,
+};
+
+pub fn part_one(contents: &str) -> Result<u64> {
     let mut input = load_endpoints(contents)?;
     input.retain(|coords| coords[0][0] == coords[1][0] || coords[0][1] == coords[1][1]);
-    Ok(count_overlap(input.as_slice()))
+    Ok(count_overlap(input.as_slice()) as u64)
 }
 
-pub fn part_two(contents: &str) -> Result<usize> {
-    Ok(count_overlap(load_endpoints(contents)?.as_slice()))
+pub fn part_two(contents: &str) -> Result<u64> {
+    Ok(count_overlap(load_endpoints(contents)?.as_slice()) as u64)
+}
+
+/// Checks that every line is a segment like `0,9 -> 5,9`
+pub fn lint(contents: &str) -> Vec<Problem> {
+    let point = |point: &str| {
+        point
+            .split_once(',')
+            .is_some_and(|(x, y)| x.parse::<usize>().is_ok() && y.parse::<usize>().is_ok())
+    };
+    let mut problems: Vec<_> = contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| {
+            !line
+                .split_once(" -> ")
+                .is_some_and(|(from, to)| point(from) && point(to))
+        })
+        .map(|(i, line)| {
+            Problem::new(
+                i + 1,
+                format!("'{}' is not a segment like 'x,y -> x,y'", line),
+            )
+        })
+        .collect();
+    if contents.is_empty() {
+        problems.push(Problem::new(1, "no segments".to_string()));
+    }
+    problems
 }
 
 fn count_overlap(endpoints: &[[[usize; 2]; 2]]) -> usize {
@@ -38,7 +77,7 @@ fn count_overlap(endpoints: &[[[usize; 2]; 2]]) -> usize {
         .sum()
 }
 
-fn load_endpoints(content: &str) -> std::result::Result<Vec<[[usize; 2]; 2]>, ParseIntError> {
+fn load_endpoints(content: &str) -> core::result::Result<Vec<[[usize; 2]; 2]>, ParseIntError> {
     content
         .lines()
         .map(|s| {
@@ -67,6 +106,25 @@ mod tests {
 
     use std::fs::read_to_string;
 
+    #[test]
+    fn test_lint() {
+        let contents = read_to_string("fixtures/vents.txt").expect("missing fixture");
+        assert_eq!(Vec::<Problem>::new(), lint(contents.as_str()));
+        assert_eq!(
+            vec![
+                Problem::new(
+                    2,
+                    "'8,0 -> 0' is not a segment like 'x,y -> x,y'".to_string()
+                ),
+                Problem::new(
+                    3,
+                    "'9,4 3,4' is not a segment like 'x,y -> x,y'".to_string()
+                ),
+            ],
+            lint("0,9 -> 5,9\n8,0 -> 0\n9,4 3,4")
+        );
+    }
+
     const SAMPLE_INPUT: &[[[usize; 2]; 2]] = &[
         [[0, 9], [5, 9]],
         [[8, 0], [0, 8]],
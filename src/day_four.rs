@@ -1,62 +1,147 @@
-use std::{ops::ControlFlow, str::FromStr};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{fmt, ops::ControlFlow, str::FromStr};
 
 use anyhow::Result;
 
-pub const INPUT_PATH: &str = "inputs/day/4/input";
+use crate::checked::{self, Overflow};
+use crate::lint::{fields, words, Problem};
+use crate::metadata::{Metadata, Tag};
 
-pub fn part_one(contents: &str) -> Result<usize> {
-    Ok(Bingo::from_str(contents)?.play())
+pub const INPUT_PATH: &str = "inputs/2021/day/4/input";
+
+pub const METADATA: Metadata = Metadata {
+    title: "Giant Squid",
+    author: "medwards",
+    tags: &[Tag::Grid, Tag::Simulation],
+    examples: true,
+};
+
+pub fn part_one(contents: &str) -> Result<u64> {
+    Ok(Bingo::from_str(contents)?.play()?)
+}
+
+pub fn part_two(contents: &str) -> Result<u64> {
+    Ok(Bingo::from_str(contents)?.play_to_lose()?)
 }
 
-pub fn part_two(contents: &str) -> Result<usize> {
-    Ok(Bingo::from_str(contents)?.play_to_lose())
+pub fn inspect(contents: &str) -> Result<String> {
+    Ok(Bingo::from_str(contents)?.to_string())
+}
+
+/// Checks for a line of comma separated balls and 5x5 boards of numbers, separated by blank lines
+pub fn lint(contents: &str) -> Vec<Problem> {
+    let lines: Vec<_> = contents.lines().collect();
+    let mut problems: Vec<_> = fields(lines.first().copied().unwrap_or(""), ',')
+        .filter(|(_, ball)| ball.parse::<usize>().is_err())
+        .map(|(column, ball)| Problem::new(1, format!("'{}' is not a ball", ball)).at(column))
+        .collect();
+    if lines.get(1).is_some_and(|line| !line.is_empty()) {
+        problems.push(Problem::new(
+            2,
+            "expected a blank line after the balls".to_string(),
+        ));
+    }
+
+    // a blank line after the last ends the last board
+    let mut board: Vec<usize> = Vec::new();
+    let mut boards = 0;
+    lines
+        .iter()
+        .copied()
+        .enumerate()
+        .skip(1)
+        .chain(core::iter::once((lines.len(), "")))
+        .for_each(|(i, line)| {
+            if line.is_empty() {
+                if !board.is_empty() && board.len() != 5 {
+                    problems.push(Problem::new(
+                        board[0],
+                        format!("board has {} rows, expected 5", board.len()),
+                    ));
+                }
+                boards += usize::from(!board.is_empty());
+                board.clear();
+                return;
+            }
+            board.push(i + 1);
+            let squares: Vec<_> = words(line).collect();
+            if squares.len() != 5 {
+                problems.push(Problem::new(
+                    i + 1,
+                    format!("board row has {} numbers, expected 5", squares.len()),
+                ));
+            }
+            squares
+                .iter()
+                .filter(|(_, square)| square.parse::<usize>().is_err())
+                .for_each(|(column, square)| {
+                    problems.push(
+                        Problem::new(i + 1, format!("'{}' is not a number", square)).at(*column),
+                    )
+                });
+        });
+    if boards == 0 {
+        problems.push(Problem::new(lines.len().max(1), "no boards".to_string()));
+    }
+    problems.sort_by_key(|problem| (problem.line, problem.column));
+    problems
 }
 
 #[derive(Debug, PartialEq)]
 struct Bingo {
-    balls: Vec<usize>,
+    balls: Vec<u64>,
     boards: Vec<Board>,
 }
 
 impl Bingo {
-    fn play(&mut self) -> usize {
+    fn play(&mut self) -> core::result::Result<u64, Overflow> {
         for ball in self.balls.iter() {
-            let score = self.boards.iter_mut().fold(0, |score, board| {
+            log::trace!(target: "day4", "ball {}", ball);
+            let score = self.boards.iter_mut().try_fold(0u64, |score, board| {
                 board.mark(*ball);
                 if board.is_complete() {
-                    score + (board.score() * *ball)
+                    let board_score = board.score()?.checked_mul(*ball).ok_or(Overflow)?;
+                    score.checked_add(board_score).ok_or(Overflow)
                 } else {
-                    score
+                    Ok(score)
                 }
-            });
+            })?;
             if score > 0 {
-                return score;
+                log::debug!(target: "day4", "first board completed by ball {}", ball);
+                return Ok(score);
             }
         }
-        0
+        Ok(0)
     }
 
-    fn play_to_lose(&mut self) -> usize {
-        let boards_result =
-            self.balls
-                .iter()
-                .try_fold((self.boards.clone(), 0), |(mut boards, score), ball| {
-                    let keep_board = boards.len() == 1;
-                    boards.iter_mut().for_each(|board| board.mark(*ball));
-                    boards.retain(|board| keep_board || !board.is_complete());
-                    if boards.len() == 1 {
-                        if let Some(board) = boards.get(0) {
-                            if board.is_complete() {
-                                let score = board.score() * *ball;
-                                return ControlFlow::Break((boards, score));
-                            }
+    fn play_to_lose(&mut self) -> core::result::Result<u64, Overflow> {
+        let boards_result = self.balls.iter().try_fold(
+            (self.boards.clone(), Ok::<_, Overflow>(0)),
+            |(mut boards, score), ball| {
+                let keep_board = boards.len() == 1;
+                boards.iter_mut().for_each(|board| board.mark(*ball));
+                boards.retain(|board| keep_board || !board.is_complete());
+                log::trace!(target: "day4", "ball {} leaves {} boards", ball, boards.len());
+                if boards.len() == 1 {
+                    if let Some(board) = boards.get(0) {
+                        if board.is_complete() {
+                            let score = board
+                                .score()
+                                .and_then(|score| score.checked_mul(*ball).ok_or(Overflow));
+                            return ControlFlow::Break((boards, score));
                         }
                     }
-                    ControlFlow::Continue((boards, score))
-                });
+                }
+                ControlFlow::Continue((boards, score))
+            },
+        );
         match boards_result {
             ControlFlow::Break((_, score)) => score,
-            _ => 0,
+            _ => Ok(0),
         }
     }
 }
@@ -64,14 +149,14 @@ impl Bingo {
 impl FromStr for Bingo {
     type Err = anyhow::Error;
 
-    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+    fn from_str(s: &str) -> core::result::Result<Self, Self::Err> {
         let mut lines = s.lines();
-        let balls: Vec<usize> = lines
+        let balls: Vec<u64> = lines
             .next()
             .ok_or_else(|| anyhow::anyhow!("Missing balls line"))?
             .split(',')
             .map(|b| b.parse())
-            .collect::<std::result::Result<Vec<_>, _>>()?;
+            .collect::<core::result::Result<Vec<_>, _>>()?;
 
         let bingo = Bingo {
             balls,
@@ -92,7 +177,7 @@ impl FromStr for Bingo {
                 let mut squares_index = squares_index
                     + line
                         .split(' ')
-                        .flat_map(|s| s.parse::<usize>())
+                        .flat_map(|s| s.parse::<u64>())
                         .enumerate()
                         .map(|(i, val)| {
                             board.squares[squares_index + i] = val;
@@ -114,14 +199,26 @@ impl FromStr for Bingo {
     }
 }
 
+impl fmt::Display for Bingo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let balls: Vec<_> = self.balls.iter().map(|b| b.to_string()).collect();
+        writeln!(f, "balls: {}", balls.join(","))?;
+        self.boards.iter().enumerate().try_for_each(|(i, board)| {
+            writeln!(f)?;
+            writeln!(f, "board {}:", i + 1)?;
+            write!(f, "{}", board)
+        })
+    }
+}
+
 #[derive(Clone, Debug, PartialEq)]
 struct Board {
-    squares: [usize; 25],
+    squares: [u64; 25],
     marked: [u8; 25],
 }
 
 impl Board {
-    fn mark(&mut self, value: usize) {
+    fn mark(&mut self, value: u64) {
         self.squares.iter().enumerate().for_each(|(i, s)| {
             if *s == value {
                 self.marked[i] = 1
@@ -176,13 +273,37 @@ impl Board {
         false
     }
 
-    fn score(&self) -> usize {
+    fn score(&self) -> core::result::Result<u64, Overflow> {
+        checked::sum(
+            self.squares
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| self.marked[*i] != 1)
+                .map(|(_, value)| *value),
+        )
+    }
+}
+
+impl fmt::Display for Board {
+    // marked squares are wrapped in brackets
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.squares
-            .iter()
+            .chunks(5)
             .enumerate()
-            .filter(|(i, _)| self.marked[*i] != 1)
-            .map(|(_, value)| value)
-            .sum()
+            .try_for_each(|(row, squares)| {
+                let row: Vec<_> = squares
+                    .iter()
+                    .enumerate()
+                    .map(|(col, square)| {
+                        if self.marked[(row * 5) + col] == 1 {
+                            format!("[{:>2}]", square)
+                        } else {
+                            format!(" {:>2} ", square)
+                        }
+                    })
+                    .collect();
+                writeln!(f, "{}", row.join(""))
+            })
     }
 }
 
@@ -214,14 +335,45 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_lint() {
+        let contents = read_to_string("fixtures/bingo.txt").expect("missing fixture");
+        assert_eq!(Vec::<Problem>::new(), lint(contents.as_str()));
+
+        let problems: Vec<_> = lint("7,x,9\n1 2 3 4 5\n1 2 3 4 y\n1 2 3 4\n\n\n1 2 3 4 5\n")
+            .iter()
+            .map(Problem::to_string)
+            .collect();
+        assert_eq!(
+            vec![
+                "line 1, column 3: 'x' is not a ball",
+                "line 2: expected a blank line after the balls",
+                "line 2: board has 3 rows, expected 5",
+                "line 3, column 9: 'y' is not a number",
+                "line 4: board row has 4 numbers, expected 5",
+                "line 7: board has 1 rows, expected 5",
+            ],
+            problems
+        );
+        assert_eq!(
+            vec![Problem::new(1, "no boards".to_string())],
+            lint("7,4,9")
+        );
+    }
+
     #[test]
     fn test_bingo_play() {
-        assert_eq!(4512, expected_bingo().play());
+        assert_eq!(Ok(4512), expected_bingo().play());
+
+        let mut bingo = expected_bingo();
+        bingo.balls = vec![u64::MAX];
+        bingo.boards[0].squares[..5].fill(u64::MAX);
+        assert_eq!(Err(Overflow), bingo.play());
     }
 
     #[test]
     fn test_bingo_play_to_lose() {
-        assert_eq!(1924, expected_bingo().play_to_lose());
+        assert_eq!(Ok(1924), expected_bingo().play_to_lose());
     }
 
     #[test]
@@ -234,6 +386,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_board_display() {
+        let mut board = expected_bingo().boards.remove(0);
+        board.mark(22);
+        board.mark(19);
+
+        assert_eq!(
+            "[22] 13  17  11   0 \n  8   2  23   4  24 \n 21   9  14  16   7 \n  6  10   3  18   5 \n  1  12  20  15 [19]\n",
+            board.to_string()
+        );
+    }
+
     #[test]
     fn test_board_is_complete() {
         let incomplete_board = Board {
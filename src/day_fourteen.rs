@@ -1,29 +1,50 @@
-use std::cmp::max;
+use alloc::{string::ToString, vec, vec::Vec};
+use core::cmp::max;
 
 use anyhow::Result;
 
-pub const INPUT_PATH: &str = "inputs/day/14/input";
+use crate::checked::{self, Overflow};
+use crate::diagnostic::Diagnostic;
+use crate::lint::Problem;
+use crate::metadata::{Metadata, Tag};
+use crate::params::{Parameter, Params};
+
+pub const INPUT_PATH: &str = "inputs/2021/day/14/input";
+
+pub const METADATA: Metadata = Metadata {
+    title: "Extended Polymerization",
+    author: "medwards",
+    tags: &[Tag::Simulation],
+    examples: true,
+};
+
+pub const PARAMETERS: &[Parameter] = &[Parameter {
+    name: "steps",
+    about: "pair insertion steps to apply",
+    part_one: Some(10),
+    part_two: Some(40),
+}];
+
+pub fn part_one(contents: &str) -> Result<u64> {
+    part_one_with(contents, &Params::defaults(PARAMETERS, 1))
+}
 
-pub fn part_one(contents: &str) -> Result<usize> {
-    let (template, rules) = load_polymerization(contents)?;
+pub fn part_two(contents: &str) -> Result<u64> {
+    part_two_with(contents, &Params::defaults(PARAMETERS, 2))
+}
 
-    let counts = process_polymer(template.as_slice(), rules.as_slice(), 10);
+pub fn part_one_with(contents: &str, params: &Params) -> Result<u64> {
+    element_difference(contents, params.get("steps"))
+}
 
-    Ok(counts
-        .iter()
-        .max()
-        .ok_or_else(|| anyhow::anyhow!("empty polymer"))?
-        - counts
-            .iter()
-            .filter(|count| **count != 0)
-            .min()
-            .ok_or_else(|| anyhow::anyhow!("empty_polymer"))?)
+pub fn part_two_with(contents: &str, params: &Params) -> Result<u64> {
+    element_difference(contents, params.get("steps"))
 }
 
-pub fn part_two(contents: &str) -> Result<usize> {
+pub fn element_difference(contents: &str, steps: usize) -> Result<u64> {
     let (template, rules) = load_polymerization(contents)?;
 
-    let counts = process_polymer(template.as_slice(), rules.as_slice(), 40);
+    let counts = process_polymer(template.as_slice(), rules.as_slice(), steps)?;
 
     Ok(counts
         .iter()
@@ -36,35 +57,51 @@ pub fn part_two(contents: &str) -> Result<usize> {
             .ok_or_else(|| anyhow::anyhow!("empty_polymer"))?)
 }
 
-fn process_polymer(template: &[u8], rules: &[(u8, u8, u8)], iterations: usize) -> Vec<usize> {
+// pair counts roughly double every step, past u64 after about 60 steps
+fn process_polymer(
+    template: &[u8],
+    rules: &[(u8, u8, u8)],
+    iterations: usize,
+) -> core::result::Result<Vec<u64>, Overflow> {
     let pairs = template_to_pairs(template, rules);
 
-    let pairs = (0..iterations).fold(pairs, |pairs, _| apply_rules(&pairs, rules));
+    let pairs = (0..iterations).try_fold(pairs, |pairs, _| apply_rules(&pairs, rules))?;
 
     count_from_pairs(&pairs, template)
 }
 
-fn apply_rules(pairs: &Vec<Vec<usize>>, rules: &[(u8, u8, u8)]) -> Vec<Vec<usize>> {
+fn apply_rules(
+    pairs: &Vec<Vec<u64>>,
+    rules: &[(u8, u8, u8)],
+) -> core::result::Result<Vec<Vec<u64>>, Overflow> {
     let mut result = vec![vec![0; pairs.len()]; pairs.len()];
-    pairs.iter().enumerate().for_each(|(i, row)| {
-        row.iter().enumerate().for_each(|(j, count)| {
+    pairs.iter().enumerate().try_for_each(|(i, row)| {
+        row.iter().enumerate().try_for_each(|(j, count)| {
             if let Some((_, _, output)) = rules
                 .iter()
                 .find(|rule| rule.0 == i as u8 + b'A' && rule.1 == j as u8 + b'A')
             {
                 let output_index = (output - b'A') as usize;
 
-                *result.get_mut(i).unwrap().get_mut(output_index).unwrap() += *count;
-                *result.get_mut(output_index).unwrap().get_mut(j).unwrap() += *count;
+                add(&mut result[i][output_index], *count)?;
+                add(&mut result[output_index][j], *count)
             } else {
-                *result.get_mut(i).unwrap().get_mut(j).unwrap() += *count;
+                add(&mut result[i][j], *count)
             }
         })
-    });
-    result
+    })?;
+    Ok(result)
+}
+
+fn add(total: &mut u64, count: u64) -> core::result::Result<(), Overflow> {
+    *total = total.checked_add(count).ok_or(Overflow)?;
+    Ok(())
 }
 
-fn count_from_pairs(pairs: &Vec<Vec<usize>>, template: &[u8]) -> Vec<usize> {
+fn count_from_pairs(
+    pairs: &Vec<Vec<u64>>,
+    template: &[u8],
+) -> core::result::Result<Vec<u64>, Overflow> {
     let mut counts = vec![0; pairs.len()];
     template
         .last()
@@ -74,11 +111,11 @@ fn count_from_pairs(pairs: &Vec<Vec<usize>>, template: &[u8]) -> Vec<usize> {
     pairs
         .iter()
         .enumerate()
-        .for_each(|(i, row)| counts[i] += row.iter().sum::<usize>());
-    counts
+        .try_for_each(|(i, row)| add(&mut counts[i], checked::sum(row.iter().copied())?))?;
+    Ok(counts)
 }
 
-fn template_to_pairs(template: &[u8], rules: &[(u8, u8, u8)]) -> Vec<Vec<usize>> {
+fn template_to_pairs(template: &[u8], rules: &[(u8, u8, u8)]) -> Vec<Vec<u64>> {
     let max = (*max(template.iter().max(), rules.iter().map(|(_, _, o)| o).max()).unwrap_or(&0)
         - b'A') as usize;
     let mut counts = vec![vec![0; max + 1]; max + 1];
@@ -92,39 +129,48 @@ fn template_to_pairs(template: &[u8], rules: &[(u8, u8, u8)]) -> Vec<Vec<usize>>
     counts
 }
 
+const RULE_HINT: &str = "rules are pair insertions, ie CH -> B";
+
 fn load_polymerization(contents: &str) -> Result<(Vec<u8>, Vec<(u8, u8, u8)>)> {
-    let mut iter = contents.lines();
+    let mut iter = contents.lines().enumerate();
 
     let template = iter
-        .nth(0)
-        .ok_or_else(|| anyhow::anyhow!("empty input"))?
+        .next()
+        .ok_or_else(|| {
+            let problem = Problem::new(1, "empty input".to_string());
+            Diagnostic::new("load_polymerization", problem, "")
+                .hint("the first line is the polymer template, ie NNCB")
+        })?
+        .1
         .as_bytes()
         .to_vec();
 
-    let rules: Result<Vec<_>> = iter
-        .skip(1)
-        .map(|line| {
-            let parts = line
-                .split_once(" -> ")
-                .ok_or_else(|| anyhow::anyhow!("Missing rule delimiter"))?;
-            let output = parts
-                .1
-                .as_bytes()
-                .get(0)
-                .ok_or_else(|| anyhow::anyhow!("Missing rule output"))?;
-            let left = parts
-                .0
-                .as_bytes()
-                .get(0)
-                .ok_or_else(|| anyhow::anyhow!("Missing rule left input"))?;
-            let right = parts
-                .0
-                .as_bytes()
-                .get(1)
-                .ok_or_else(|| anyhow::anyhow!("Missing rule right input"))?;
-            Ok((*left, *right, *output))
-        })
-        .collect();
+    let rules: core::result::Result<Vec<_>, Diagnostic> =
+        iter.skip(1)
+            .map(|(i, line)| {
+                let diagnostic = |column, message: &str| {
+                    let problem = Problem::new(i + 1, message.to_string()).at(column);
+                    Diagnostic::new("load_polymerization", problem, line).hint(RULE_HINT)
+                };
+                let parts = line
+                    .split_once(" -> ")
+                    .ok_or_else(|| diagnostic(1, "missing rule delimiter ' -> '"))?;
+                let output = parts.1.as_bytes().first().ok_or_else(|| {
+                    diagnostic(parts.0.chars().count() + 5, "missing rule output")
+                })?;
+                let left = parts
+                    .0
+                    .as_bytes()
+                    .first()
+                    .ok_or_else(|| diagnostic(1, "missing rule left input"))?;
+                let right = parts
+                    .0
+                    .as_bytes()
+                    .get(1)
+                    .ok_or_else(|| diagnostic(2, "missing rule right input"))?;
+                Ok((*left, *right, *output))
+            })
+            .collect();
 
     Ok((template, rules?))
 }
@@ -135,6 +181,27 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_load_polymerization_diagnostics() {
+        let problem = |contents: &str| {
+            let error = load_polymerization(contents).unwrap_err();
+            error
+                .downcast_ref::<Diagnostic>()
+                .unwrap()
+                .problem
+                .to_string()
+        };
+        assert_eq!("line 1: empty input", problem(""));
+        assert_eq!(
+            "line 4, column 1: missing rule delimiter ' -> '",
+            problem("NNCB\n\nCH -> B\nHH-> N")
+        );
+        assert_eq!(
+            "line 3, column 7: missing rule output",
+            problem("NNCB\n\nCH -> ")
+        );
+    }
+
     #[test]
     fn test_template_to_pairs() {
         let (template, rules) =
@@ -158,17 +225,17 @@ mod tests {
 
         let expected = template_to_pairs("NCNBCHB".as_bytes(), rules.as_slice());
         let mut old_counts = template_to_pairs(template.as_slice(), rules.as_slice());
-        let counts = apply_rules(&old_counts, rules.as_slice());
+        let counts = apply_rules(&old_counts, rules.as_slice()).unwrap();
         assert_eq!(expected, counts);
 
         let expected = template_to_pairs("NBCCNBBBCBHCB".as_bytes(), rules.as_slice());
         old_counts = counts;
-        let counts = apply_rules(&old_counts, rules.as_slice());
+        let counts = apply_rules(&old_counts, rules.as_slice()).unwrap();
         assert_eq!(expected, counts);
 
         let expected = template_to_pairs("NBBBCNCCNBBNBNBBCHBHHBCHB".as_bytes(), rules.as_slice());
         old_counts = counts;
-        let counts = apply_rules(&old_counts, rules.as_slice());
+        let counts = apply_rules(&old_counts, rules.as_slice()).unwrap();
         assert_eq!(expected, counts);
 
         let expected = template_to_pairs(
@@ -176,7 +243,7 @@ mod tests {
             rules.as_slice(),
         );
         old_counts = counts;
-        let counts = apply_rules(&old_counts, rules.as_slice());
+        let counts = apply_rules(&old_counts, rules.as_slice()).unwrap();
         assert_eq!(expected, counts);
     }
 
@@ -202,6 +269,19 @@ mod tests {
                 &pairs,
                 "NBBNBNBBCCNBCNCCNBBNBBNBBBNBBNBBCBHCBHHNHCBBCBHCB".as_bytes()
             )
+            .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_element_difference() {
+        let contents = read_to_string("fixtures/polymer.txt").unwrap();
+        assert_eq!(
+            2188189693529,
+            element_difference(contents.as_str(), 40).unwrap()
         );
+        assert!(element_difference(contents.as_str(), 80)
+            .unwrap_err()
+            .is::<Overflow>());
     }
 }
@@ -1,12 +1,40 @@
+use alloc::{vec, vec::Vec};
+
 use anyhow::Result;
 
-pub const INPUT_PATH: &str = "inputs/day/9/input";
+use crate::checked::{self, Overflow};
+use crate::metadata::{Metadata, Tag};
+use crate::params::{Parameter, Params};
+
+pub const INPUT_PATH: &str = "inputs/2021/day/9/input";
+
+pub const METADATA: Metadata = Metadata {
+    title: "Smoke Basin",
+    author: "medwards",
+    tags: &[Tag::Grid],
+    examples: true,
+};
+
+pub const PARAMETERS: &[Parameter] = &[Parameter {
+    name: "basins",
+    about: "largest basins to multiply",
+    part_one: None,
+    part_two: Some(3),
+}];
+
+pub fn part_one(contents: &str) -> Result<u64> {
+    part_one_with(contents, &Params::defaults(PARAMETERS, 1))
+}
+
+pub fn part_two(contents: &str) -> Result<u64> {
+    part_two_with(contents, &Params::defaults(PARAMETERS, 2))
+}
 
-pub fn part_one(contents: &str) -> Result<usize> {
-    Ok(calculate_risk_level(load_height_map(contents)?.as_slice()) as usize)
+pub fn part_one_with(contents: &str, _params: &Params) -> Result<u64> {
+    Ok(calculate_risk_level(load_height_map(contents)?.as_slice())?)
 }
 
-pub fn part_two(contents: &str) -> Result<usize> {
+pub fn part_two_with(contents: &str, params: &Params) -> Result<u64> {
     let height_map = load_height_map(contents)?;
     let mut basins = calculate_basins(
         height_map.as_slice(),
@@ -15,14 +43,39 @@ pub fn part_two(contents: &str) -> Result<usize> {
     basins.sort_unstable();
     basins.reverse();
 
-    Ok(basins.iter().take(3).product())
+    Ok(checked::product(
+        basins
+            .iter()
+            .take(params.get("basins"))
+            .map(|basin| *basin as u64),
+    )?)
 }
 
-fn calculate_risk_level(height_map: &[Vec<u32>]) -> u32 {
-    low_points(height_map)
-        .iter()
-        .map(|(_, _, height)| height + 1)
-        .sum()
+/// Both parts from one parse of the height map and one search for its low points
+pub fn solve_both(contents: &str) -> Result<(u64, u64)> {
+    let height_map = load_height_map(contents)?;
+    let low_points = low_points(height_map.as_slice());
+    let risk_level = checked::sum(
+        low_points
+            .iter()
+            .map(|(_, _, height)| u64::from(height + 1)),
+    )?;
+    let mut basins = calculate_basins(height_map.as_slice(), low_points.as_slice());
+    basins.sort_unstable();
+    basins.reverse();
+    let largest = Params::defaults(PARAMETERS, 2).get("basins");
+
+    let product = checked::product(basins.iter().take(largest).map(|basin| *basin as u64))?;
+
+    Ok((risk_level, product))
+}
+
+fn calculate_risk_level(height_map: &[Vec<u32>]) -> core::result::Result<u64, Overflow> {
+    checked::sum(
+        low_points(height_map)
+            .iter()
+            .map(|(_, _, height)| u64::from(height + 1)),
+    )
 }
 
 fn calculate_basins(height_map: &[Vec<u32>], low_points: &[(usize, usize, u32)]) -> Vec<usize> {
@@ -174,7 +227,7 @@ mod tests {
             vec![9, 8, 9, 9, 9, 6, 5, 6, 7, 8],
         ];
 
-        assert_eq!(15, calculate_risk_level(input.as_slice()));
+        assert_eq!(Ok(15), calculate_risk_level(input.as_slice()));
     }
 
     #[test]
@@ -191,6 +244,12 @@ mod tests {
         assert_eq!(expected, low_points(input.as_slice()));
     }
 
+    #[test]
+    fn test_solve_both() {
+        let contents = read_to_string("fixtures/heightmap.txt").unwrap();
+        assert_eq!((15, 1134), solve_both(contents.as_str()).unwrap());
+    }
+
     #[test]
     fn test_load_height_map() {
         let expected = vec![
@@ -1,46 +1,86 @@
-use anyhow::Result;
+use alloc::vec;
 
-use crate::load_integers;
+use anyhow::{anyhow, Result};
 
-pub const INPUT_PATH: &str = "inputs/day/1/input";
+use crate::metadata::Metadata;
+use crate::params::{Parameter, Params};
+use crate::{process_results, stream_integers};
 
-pub fn part_one(contents: &str) -> Result<usize> {
-    let nums = load_integers(contents)?;
-    Ok(count_increases(nums.as_slice()))
+pub const INPUT_PATH: &str = "inputs/2021/day/1/input";
+
+pub const METADATA: Metadata = Metadata {
+    title: "Sonar Sweep",
+    author: "medwards",
+    tags: &[],
+    examples: true,
+};
+
+pub const PARAMETERS: &[Parameter] = &[Parameter {
+    name: "window",
+    about: "measurements summed before comparing",
+    part_one: Some(1),
+    part_two: Some(3),
+}];
+
+pub fn part_one(contents: &str) -> Result<u64> {
+    part_one_with(contents, &Params::defaults(PARAMETERS, 1))
 }
 
-pub fn part_two(path: &str) -> Result<usize> {
-    let nums = load_integers(path)?;
-    Ok(count_window_increases(nums.as_slice()))
+pub fn part_two(contents: &str) -> Result<u64> {
+    part_two_with(contents, &Params::defaults(PARAMETERS, 2))
 }
 
-fn count_increases(nums: &[usize]) -> usize {
-    let pairs_iter = nums.iter().zip(nums.iter().skip(1));
-    pairs_iter.fold(0, |count, (left, right)| {
-        count + if left < right { 1 } else { 0 }
-    })
+pub fn part_one_with(contents: &str, params: &Params) -> Result<u64> {
+    count_lines(contents.lines().map(Ok), params.get("window"))
+}
+
+pub fn part_two_with(contents: &str, params: &Params) -> Result<u64> {
+    count_lines(contents.lines().map(Ok), params.get("window"))
+}
+
+pub fn part_one_lines<S: AsRef<str>>(lines: impl Iterator<Item = Result<S>>) -> Result<u64> {
+    count_lines(lines, Params::defaults(PARAMETERS, 1).get("window"))
 }
 
-fn count_window_increases(nums: &[usize]) -> usize {
-    let window_sums: Vec<_> = nums.windows(3).map(|w| w.iter().sum()).collect();
+pub fn part_two_lines<S: AsRef<str>>(lines: impl Iterator<Item = Result<S>>) -> Result<u64> {
+    count_lines(lines, Params::defaults(PARAMETERS, 2).get("window"))
+}
 
-    count_increases(window_sums.as_slice())
+fn count_lines<S: AsRef<str>>(
+    lines: impl Iterator<Item = Result<S>>,
+    window: usize,
+) -> Result<u64> {
+    if window == 0 {
+        return Err(anyhow!("the window must hold at least one measurement"));
+    }
+    process_results(stream_integers(lines), |nums| {
+        count_window_increases(nums, window) as u64
+    })
+}
+
+// Neighbouring windows share all but one number, so the sum increases when the number entering
+// the window is larger than the one leaving it
+fn count_window_increases(nums: impl Iterator<Item = usize>, window: usize) -> usize {
+    let mut ring = vec![0; window];
+    nums.enumerate().fold(0, |count, (i, num)| {
+        let leaving = core::mem::replace(&mut ring[i % window], num);
+        count + if i >= window && leaving < num { 1 } else { 0 }
+    })
 }
 
 #[cfg(test)]
 mod tests {
-    use super::count_increases;
     use super::count_window_increases;
 
     const EXAMPLE_INPUT: &'static [usize] = &[199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
 
     #[test]
     fn test_example_part1() {
-        assert_eq!(7, count_increases(EXAMPLE_INPUT));
+        assert_eq!(7, count_window_increases(EXAMPLE_INPUT.iter().copied(), 1));
     }
 
     #[test]
     fn test_example_part2() {
-        assert_eq!(5, count_window_increases(EXAMPLE_INPUT));
+        assert_eq!(5, count_window_increases(EXAMPLE_INPUT.iter().copied(), 3));
     }
 }
@@ -1,55 +1,63 @@
-use std::cmp::{max, min};
-use std::num::ParseIntError;
+use alloc::vec::Vec;
+use core::cmp::{max, min};
+use core::num::ParseIntError;
 
 use anyhow::Result;
 
-pub const INPUT_PATH: &str = "inputs/day/7/input";
+use crate::checked::{self, Overflow};
+use crate::metadata::Metadata;
 
-pub fn part_one(contents: &str) -> Result<usize> {
-    let crabs: std::result::Result<Vec<usize>, ParseIntError> =
+pub const INPUT_PATH: &str = "inputs/2021/day/7/input";
+
+pub const METADATA: Metadata = Metadata {
+    title: "The Treachery of Whales",
+    author: "medwards",
+    tags: &[],
+    examples: true,
+};
+
+pub fn part_one(contents: &str) -> Result<u64> {
+    let crabs: core::result::Result<Vec<u64>, ParseIntError> =
         contents.trim().split(',').map(|s| s.parse()).collect();
-    Ok(least_fuel(crabs?.as_slice()))
+    Ok(least_fuel(crabs?.as_slice())?)
 }
 
-pub fn part_two(contents: &str) -> Result<usize> {
-    let crabs: std::result::Result<Vec<usize>, ParseIntError> =
+pub fn part_two(contents: &str) -> Result<u64> {
+    let crabs: core::result::Result<Vec<u64>, ParseIntError> =
         contents.trim().split(',').map(|s| s.parse()).collect();
-    Ok(least_real_fuel(crabs?.as_slice()))
+    Ok(least_real_fuel(crabs?.as_slice())?)
 }
 
-fn least_fuel(crabs: &[usize]) -> usize {
+fn least_fuel(crabs: &[u64]) -> core::result::Result<u64, Overflow> {
     let crab_max = crabs.iter().max().expect("No crabs!");
     (0..=*crab_max)
-        .map(|pos| {
-            crabs
-                .iter()
-                .map(|crab| max(*crab, pos) - min(*crab, pos))
-                .sum()
-        })
-        .fold(0, |min_fuel, fuel_sum| {
-            if fuel_sum < min_fuel || min_fuel == 0 {
+        .map(|pos| checked::sum(crabs.iter().map(|crab| max(*crab, pos) - min(*crab, pos))))
+        .try_fold(0, |min_fuel, fuel_sum| {
+            let fuel_sum = fuel_sum?;
+            Ok(if fuel_sum < min_fuel || min_fuel == 0 {
                 fuel_sum
             } else {
                 min_fuel
-            }
+            })
         })
 }
 
-fn least_real_fuel(crabs: &[usize]) -> usize {
+fn least_real_fuel(crabs: &[u64]) -> core::result::Result<u64, Overflow> {
     let crab_max = crabs.iter().max().expect("No crabs!");
     (0..=*crab_max)
         .map(|pos| {
-            crabs
-                .iter()
-                .map(|crab| (1..=(max(*crab, pos) - min(*crab, pos))).sum::<usize>())
-                .sum()
+            crabs.iter().try_fold(0u64, |fuel_sum, crab| {
+                let fuel = checked::sum(1..=(max(*crab, pos) - min(*crab, pos)))?;
+                fuel_sum.checked_add(fuel).ok_or(Overflow)
+            })
         })
-        .fold(0, |min_fuel, fuel_sum| {
-            if fuel_sum < min_fuel || min_fuel == 0 {
+        .try_fold(0, |min_fuel, fuel_sum| {
+            let fuel_sum = fuel_sum?;
+            Ok(if fuel_sum < min_fuel || min_fuel == 0 {
                 fuel_sum
             } else {
                 min_fuel
-            }
+            })
         })
 }
 
@@ -60,12 +68,12 @@ mod tests {
     #[test]
     fn test_least_fuel() {
         let crabs = &[16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
-        assert_eq!(37, least_fuel(crabs));
+        assert_eq!(Ok(37), least_fuel(crabs));
     }
 
     #[test]
     fn test_least_real_fuel() {
         let crabs = &[16, 1, 2, 0, 4, 2, 7, 1, 2, 14];
-        assert_eq!(168, least_real_fuel(crabs));
+        assert_eq!(Ok(168), least_real_fuel(crabs));
     }
 }
@@ -1,38 +1,93 @@
-use std::num::ParseIntError;
+use alloc::{format, string::ToString, vec::Vec};
+use anyhow::{anyhow, Result};
 
-use anyhow::Result;
+use crate::checked::{self, Overflow};
+use crate::lint::{fields, Problem};
+use crate::metadata::{Metadata, Tag};
+use crate::params::{Parameter, Params};
 
-pub const INPUT_PATH: &str = "inputs/day/6/input";
+pub const INPUT_PATH: &str = "inputs/2021/day/6/input";
 
-pub fn part_one(contents: &str) -> Result<usize> {
-    let fish: std::result::Result<Vec<usize>, ParseIntError> =
-        contents.trim().split(',').map(|s| s.parse()).collect();
-    Ok(simulate(fish?.as_slice(), 80))
+pub const METADATA: Metadata = Metadata {
+    title: "Lanternfish",
+    author: "medwards",
+    tags: &[Tag::Simulation],
+    examples: true,
+};
+
+pub const PARAMETERS: &[Parameter] = &[Parameter {
+    name: "days",
+    about: "days of spawning to simulate",
+    part_one: Some(80),
+    part_two: Some(256),
+}];
+
+pub fn part_one(contents: &str) -> Result<u64> {
+    part_one_with(contents, &Params::defaults(PARAMETERS, 1))
+}
+
+pub fn part_two(contents: &str) -> Result<u64> {
+    part_two_with(contents, &Params::defaults(PARAMETERS, 2))
+}
+
+pub fn part_one_with(contents: &str, params: &Params) -> Result<u64> {
+    count_fish(contents, params.get("days"))
+}
+
+pub fn part_two_with(contents: &str, params: &Params) -> Result<u64> {
+    count_fish(contents, params.get("days"))
+}
+
+pub fn count_fish(contents: &str, days: usize) -> Result<u64> {
+    let fish: Result<Vec<usize>> = contents.trim().split(',').map(age).collect();
+    Ok(simulate(fish?.as_slice(), days)?)
 }
 
-pub fn part_two(contents: &str) -> Result<usize> {
-    let fish: std::result::Result<Vec<usize>, ParseIntError> =
-        contents.trim().split(',').map(|s| s.parse()).collect();
-    Ok(simulate(fish?.as_slice(), 256))
+// `simulate` counts the fish of each age in a slot of its own
+fn age(s: &str) -> Result<usize> {
+    match s.parse()? {
+        age @ 0..=8 => Ok(age),
+        age => Err(anyhow!("'{}' is not an age from 0 to 8", age)),
+    }
 }
 
-fn simulate(fish: &[usize], days: usize) -> usize {
-    let mut fish_generations: [usize; 9] = [0; 9];
+/// Checks for a single line of comma separated ages from 0 to 8
+pub fn lint(contents: &str) -> Vec<Problem> {
+    let mut lines = contents.lines();
+    let mut problems: Vec<_> = fields(lines.next().unwrap_or(""), ',')
+        .filter(|(_, age)| !matches!(age.parse::<usize>(), Ok(0..=8)))
+        .map(|(column, age)| {
+            Problem::new(1, format!("'{}' is not an age from 0 to 8", age)).at(column)
+        })
+        .collect();
+    if lines.next().is_some() {
+        problems.push(Problem::new(
+            2,
+            "expected the ages on a single line".to_string(),
+        ));
+    }
+    problems
+}
+
+// the school grows exponentially, past u64 after about 440 days
+fn simulate(fish: &[usize], days: usize) -> core::result::Result<u64, Overflow> {
+    let mut fish_generations: [u64; 9] = [0; 9];
     fish.iter().for_each(|fish| {
         fish_generations[*fish] += 1;
     });
-    (0..days).for_each(|_| progress_day(&mut fish_generations));
-    fish_generations.iter().sum()
+    (0..days).try_for_each(|_| progress_day(&mut fish_generations))?;
+    checked::sum(fish_generations)
 }
 
-fn progress_day(generations: &mut [usize; 9]) {
+fn progress_day(generations: &mut [u64; 9]) -> core::result::Result<(), Overflow> {
     let resetting_fish = generations[0];
     (1..=8).for_each(|generation| {
         let fish = generations[generation];
         generations[generation - 1] = fish;
     });
-    generations[6] += resetting_fish;
+    generations[6] = generations[6].checked_add(resetting_fish).ok_or(Overflow)?;
     generations[8] = resetting_fish;
+    Ok(())
 }
 
 #[cfg(test)]
@@ -41,17 +96,42 @@ mod tests {
 
     const SAMPLE: &[usize] = &[3, 4, 3, 1, 2];
 
+    #[test]
+    fn test_lint() {
+        assert_eq!(Vec::<Problem>::new(), lint("3,4,3,1,2"));
+        assert_eq!(
+            vec![
+                Problem::new(1, "'9' is not an age from 0 to 8".to_string()).at(3),
+                Problem::new(1, "'' is not an age from 0 to 8".to_string()).at(5),
+                Problem::new(2, "expected the ages on a single line".to_string()),
+            ],
+            lint("3,9,,1\n2")
+        );
+    }
+
+    #[test]
+    fn test_count_fish() {
+        assert_eq!(5934, count_fish("3,4,3,1,2\n", 80).unwrap());
+        assert_eq!(
+            "'9' is not an age from 0 to 8",
+            count_fish("3,9,3", 80).unwrap_err().to_string()
+        );
+        assert!(count_fish("3,-1", 80).is_err());
+    }
+
     #[test]
     fn test_simulate() {
-        assert_eq!(5934, simulate(SAMPLE, 80));
+        assert_eq!(Ok(5934), simulate(SAMPLE, 80));
+        assert_eq!(Ok(26984457539), simulate(SAMPLE, 256));
+        assert_eq!(Err(Overflow), simulate(SAMPLE, 1024));
     }
 
     #[test]
     fn test_progress_day() {
-        let mut generations: [usize; 9] = [1, 1, 2, 1, 0, 0, 0, 0, 0];
-        let expected_generations: [usize; 9] = [1, 2, 1, 0, 0, 0, 1, 0, 1];
+        let mut generations: [u64; 9] = [1, 1, 2, 1, 0, 0, 0, 0, 0];
+        let expected_generations: [u64; 9] = [1, 2, 1, 0, 0, 0, 1, 0, 1];
 
-        progress_day(&mut generations);
+        assert_eq!(Ok(()), progress_day(&mut generations));
         assert_eq!(expected_generations, generations);
     }
 }
@@ -1,19 +1,29 @@
 #![allow(warnings)]
-use std::num::ParseIntError;
+use alloc::vec::Vec;
 
 use anyhow::Result;
 
+use crate::checked::{self, Overflow};
+use crate::metadata::{Metadata, Tag};
+
 // by permission jhorstmann (jhostmann/adventofcode2021 did not have benchmarking facilities at the
 // time)
 
-pub const INPUT_PATH: &str = "inputs/day/6/input";
+pub const INPUT_PATH: &str = "inputs/2021/day/6/input";
+
+pub const METADATA: Metadata = Metadata {
+    title: "Lanternfish",
+    author: "jhorstmann",
+    tags: &[Tag::Simulation],
+    examples: false,
+};
 
-pub fn part_one(contents: &str) -> Result<usize> {
-    Ok(part1(ages(contents)?.as_slice(), 80))
+pub fn part_one(contents: &str) -> Result<u64> {
+    Ok(part1(ages(contents)?.as_slice(), 80) as u64)
 }
 
-pub fn part_two(contents: &str) -> Result<usize> {
-    Ok(part2(ages(contents)?.as_slice(), 256))
+pub fn part_two(contents: &str) -> Result<u64> {
+    Ok(part2(ages(contents)?.as_slice(), 256)?)
 }
 
 fn part1(ages: &[u32], days: i32) -> usize {
@@ -31,14 +41,14 @@ fn part1(ages: &[u32], days: i32) -> usize {
             }
         }
 
-        std::mem::swap(&mut ages, &mut new_ages)
+        core::mem::swap(&mut ages, &mut new_ages)
     }
 
     ages.len()
 }
 
-fn part2(ages: &[u32], days: usize) -> usize {
-    let mut histogram = [0_usize; 9];
+fn part2(ages: &[u32], days: usize) -> core::result::Result<u64, Overflow> {
+    let mut histogram = [0_u64; 9];
     for age in ages.iter() {
         // per jhorstmann: unnecessary
         /*
@@ -51,17 +61,22 @@ fn part2(ages: &[u32], days: usize) -> usize {
 
     for _day in 0..days {
         let mut count = histogram[0];
-        unsafe { std::ptr::copy(histogram.as_ptr().add(1), histogram.as_mut_ptr(), 8) }
+        unsafe { core::ptr::copy(histogram.as_ptr().add(1), histogram.as_mut_ptr(), 8) }
         // for i in 1..histogram.len() {
         //     histogram[i - 1] = histogram[i]
         // }
-        histogram[6] += count;
+        histogram[6] = histogram[6].checked_add(count).ok_or(Overflow)?;
         histogram[8] = count;
     }
 
-    histogram.iter().sum()
+    checked::sum(histogram)
 }
 
-fn ages(data: &str) -> std::result::Result<Vec<u32>, ParseIntError> {
-    data.split(",").map(|n| n.parse()).collect()
+fn ages(data: &str) -> Result<Vec<u32>> {
+    data.split(",")
+        .map(|n| match n.parse()? {
+            age @ 0..=8 => Ok(age),
+            age => Err(anyhow::anyhow!("'{}' is not an age from 0 to 8", age)),
+        })
+        .collect()
 }
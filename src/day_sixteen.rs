@@ -1,27 +1,197 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::fmt;
+
 use anyhow::Result;
 
-pub const INPUT_PATH: &str = "inputs/day/16/input";
+use crate::checked::{self, Overflow};
+use crate::lint::Problem;
+use crate::metadata::{Metadata, Tag};
+
+pub const INPUT_PATH: &str = "inputs/2021/day/16/input";
+
+pub const METADATA: Metadata = Metadata {
+    title: "Packet Decoder",
+    author: "medwards",
+    tags: &[Tag::Parsing],
+    examples: true,
+};
+
+pub fn part_one(contents: &str) -> Result<u64> {
+    let packets = load_packets(contents)?;
+    Ok(read_packet(packets.as_slice(), 0)?.1)
+}
+
+pub fn part_two(contents: &str) -> Result<u64> {
+    let packets = load_packets(contents)?;
+    Ok(read_packet(packets.as_slice(), 0)?.2)
+}
 
-pub fn part_one(contents: &str) -> Result<usize> {
+/// Both parts from one pass over the packets, `read_packet` sums the versions as it evaluates
+pub fn solve_both(contents: &str) -> Result<(u64, u64)> {
     let packets = load_packets(contents)?;
-    Ok(read_packet(packets.as_slice(), 0).1)
+    let (_, version_sum, value) = read_packet(packets.as_slice(), 0)?;
+    Ok((version_sum, value))
 }
 
-pub fn part_two(contents: &str) -> Result<usize> {
+pub fn inspect(contents: &str) -> Result<String> {
     let packets = load_packets(contents)?;
-    Ok(read_packet(packets.as_slice(), 0).2)
+    Ok(decode_packet(packets.as_slice(), 0)?.1.to_string())
+}
+
+/// Checks for a single line of hexadecimal digits
+pub fn lint(contents: &str) -> Vec<Problem> {
+    let mut lines = contents.lines();
+    let transmission = lines.next().unwrap_or("");
+    let mut problems: Vec<_> = transmission
+        .chars()
+        .enumerate()
+        .filter(|(_, digit)| !digit.is_ascii_hexdigit())
+        .map(|(i, digit)| {
+            Problem::new(1, format!("'{}' is not a hexadecimal digit", digit)).at(i + 1)
+        })
+        .collect();
+    if transmission.is_empty() {
+        problems.push(Problem::new(1, "empty transmission".to_string()));
+    }
+    if lines.next().is_some() {
+        problems.push(Problem::new(
+            2,
+            "expected the transmission on a single line".to_string(),
+        ));
+    }
+    problems
+}
+
+#[derive(Debug, PartialEq)]
+struct Packet {
+    version: usize,
+    packet_type: usize,
+    contents: Contents,
+}
+
+#[derive(Debug, PartialEq)]
+enum Contents {
+    Literal(u64),
+    Operator(Vec<Packet>),
+}
+
+impl Packet {
+    fn value(&self) -> core::result::Result<u64, Overflow> {
+        match &self.contents {
+            Contents::Literal(value) => Ok(*value),
+            Contents::Operator(packets) => {
+                let terms = packets
+                    .iter()
+                    .map(Packet::value)
+                    .collect::<core::result::Result<Vec<_>, _>>()?;
+                apply_operator(self.packet_type, terms.as_slice())
+            }
+        }
+    }
+
+    fn write_tree(&self, f: &mut fmt::Formatter<'_>, depth: usize) -> fmt::Result {
+        let operator = match self.packet_type {
+            0 => "sum",
+            1 => "product",
+            2 => "minimum",
+            3 => "maximum",
+            4 => "literal",
+            5 => "greater than",
+            6 => "less than",
+            7 => "equal to",
+            _ => "unknown",
+        };
+        let value = match self.value() {
+            Ok(value) => value.to_string(),
+            Err(overflow) => overflow.to_string(),
+        };
+        writeln!(
+            f,
+            "{:indent$}v{} {} = {}",
+            "",
+            self.version,
+            operator,
+            value,
+            indent = depth * 2
+        )?;
+        if let Contents::Operator(packets) = &self.contents {
+            packets
+                .iter()
+                .try_for_each(|packet| packet.write_tree(f, depth + 1))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write_tree(f, 0)
+    }
 }
 
-fn read_packet(bits: &[u32], index: usize) -> (usize, usize, usize) {
+// Same traversal as `read_packet` but keeps the packet hierarchy around for inspection
+fn decode_packet(bits: &[u32], index: usize) -> Result<(usize, Packet)> {
     let mut i = index;
-    let mut version = read_value(bits, i, 3);
+    let version = read_value(bits, i, 3);
+    i += 3;
+    let packet_type = read_value(bits, i, 3);
+    i += 3;
+
+    let contents = if packet_type == 4 {
+        let (read, literal) = read_literal(bits, i)?;
+        i += read;
+        Contents::Literal(literal)
+    } else {
+        let mut packets = vec![];
+        let length_type = read_value(bits, i, 1);
+        i += 1;
+        if length_type == 0 {
+            let mut packets_bit_len = read_value(bits, i, 15);
+            i += 15;
+            while packets_bit_len > 0 {
+                let (read, packet) = decode_packet(bits, i)?;
+                i += read;
+                packets_bit_len = packets_bit_len.checked_sub(read).ok_or_else(too_long)?;
+                packets.push(packet);
+            }
+        } else {
+            let packets_len = read_value(bits, i, 11);
+            i += 11;
+            for _ in 0..packets_len {
+                let (read, packet) = decode_packet(bits, i)?;
+                i += read;
+                packets.push(packet);
+            }
+        }
+        Contents::Operator(packets)
+    };
+
+    Ok((
+        i - index,
+        Packet {
+            version,
+            packet_type,
+            contents,
+        },
+    ))
+}
+
+// the bits read, the sum of the versions and the value of the packet at `index`
+fn read_packet(bits: &[u32], index: usize) -> Result<(usize, u64, u64)> {
+    let mut i = index;
+    let mut version = read_value(bits, i, 3) as u64;
     i += 3;
     let packet_type = read_value(bits, i, 3);
     i += 3;
 
     if packet_type == 4 {
-        let (read, result) = read_literal(bits, i);
-        ((i - index) + read, version, result)
+        let (read, result) = read_literal(bits, i)?;
+        Ok(((i - index) + read, version, result))
     } else {
         let mut results = vec![];
         let length_type = read_value(bits, i, 1);
@@ -30,56 +200,65 @@ fn read_packet(bits: &[u32], index: usize) -> (usize, usize, usize) {
             let mut packets_bit_len = read_value(bits, i, 15);
             i += 15;
             while packets_bit_len > 0 {
-                let (op_read, op_version, op_result) = read_packet(bits, i);
+                let (op_read, op_version, op_result) = read_packet(bits, i)?;
                 i += op_read;
                 results.push(op_result);
-                packets_bit_len -= op_read;
-                version += op_version;
+                packets_bit_len = packets_bit_len.checked_sub(op_read).ok_or_else(too_long)?;
+                version = version.checked_add(op_version).ok_or(Overflow)?;
             }
-            (
+            Ok((
                 i - index,
                 version,
-                apply_operator(packet_type, results.as_slice()),
-            )
+                apply_operator(packet_type, results.as_slice())?,
+            ))
         } else {
             let mut packets_len = read_value(bits, i, 11);
             i += 11;
             while packets_len > 0 {
-                let (op_read, op_version, op_result) = read_packet(bits, i);
+                let (op_read, op_version, op_result) = read_packet(bits, i)?;
                 i += op_read;
                 results.push(op_result);
                 packets_len -= 1;
-                version += op_version;
+                version = version.checked_add(op_version).ok_or(Overflow)?;
             }
-            (
+            Ok((
                 i - index,
                 version,
-                apply_operator(packet_type, results.as_slice()),
-            )
+                apply_operator(packet_type, results.as_slice())?,
+            ))
         }
     }
 }
 
-fn read_literal(bits: &[u32], index: usize) -> (usize, usize) {
+// a malformed transmission, not a result out of range
+fn too_long() -> anyhow::Error {
+    anyhow::anyhow!("sub-packets exceed their declared length")
+}
+
+// literals are unbounded, any with more than 16 groups of 4 bits set fails
+fn read_literal(bits: &[u32], index: usize) -> core::result::Result<(usize, u64), Overflow> {
     let mut i = index;
-    let mut literal = 0;
+    let mut literal: u64 = 0;
     loop {
         let loop_break = read_value(bits, i, 1) == 0;
         i += 1;
+        if literal.leading_zeros() < 4 {
+            return Err(Overflow);
+        }
         literal = literal << 4;
-        literal = literal | read_value(bits, i, 4);
+        literal |= read_value(bits, i, 4) as u64;
         i += 4;
         if loop_break {
             break;
         }
     }
-    (i - index, literal)
+    Ok((i - index, literal))
 }
 
-fn apply_operator(packet_type: usize, terms: &[usize]) -> usize {
-    match packet_type {
-        0 => terms.iter().sum(),
-        1 => terms.iter().product(),
+fn apply_operator(packet_type: usize, terms: &[u64]) -> core::result::Result<u64, Overflow> {
+    Ok(match packet_type {
+        0 => checked::sum(terms.iter().copied())?,
+        1 => checked::product(terms.iter().copied())?,
         2 => *terms.iter().min().expect("empty terms"),
         3 => *terms.iter().max().expect("empty terms"),
         // packet type 4 is for literals
@@ -105,7 +284,7 @@ fn apply_operator(packet_type: usize, terms: &[usize]) -> usize {
             }
         }
         _ => panic!("invalid packet type"),
-    }
+    })
 }
 
 fn load_packets(contents: &str) -> Result<Vec<u32>> {
@@ -161,6 +340,64 @@ fn hex(c: u8) -> Result<u8> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_lint() {
+        assert_eq!(Vec::<Problem>::new(), lint("8A004A801a8002F478"));
+        assert_eq!(
+            vec![
+                Problem::new(1, "'G' is not a hexadecimal digit".to_string()).at(3),
+                Problem::new(2, "expected the transmission on a single line".to_string()),
+            ],
+            lint("8AG0\n8A")
+        );
+        assert_eq!(
+            vec![Problem::new(1, "empty transmission".to_string())],
+            lint("")
+        );
+    }
+
+    #[test]
+    fn test_solve_both() {
+        assert_eq!((14, 3), solve_both("C200B40A82").unwrap());
+    }
+
+    #[test]
+    fn test_overflow() {
+        // literals of 16 and 17 groups of 4 bits set
+        assert_eq!(
+            u64::MAX,
+            read_packet(
+                load_packets("D3FFFFFFFFFFFFFFFFFFBC").unwrap().as_slice(),
+                0
+            )
+            .unwrap()
+            .2
+        );
+        assert_eq!(
+            Some(&Overflow),
+            read_packet(
+                load_packets("D3FFFFFFFFFFFFFFFFFFFDE").unwrap().as_slice(),
+                0
+            )
+            .unwrap_err()
+            .downcast_ref()
+        );
+        assert_eq!(Err(Overflow), apply_operator(1, &[u64::MAX, 2]));
+    }
+
+    #[test]
+    fn test_malformed() {
+        // sub-packets longer than the 10 bits their operator declares
+        let malformed = load_packets("38002B45291200").unwrap();
+        assert_eq!(
+            "sub-packets exceed their declared length",
+            read_packet(malformed.as_slice(), 0)
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(decode_packet(malformed.as_slice(), 0).is_err());
+    }
+
     #[test]
     fn test_read_value() {
         let bits = [1108168701, 187793664];
@@ -171,57 +408,111 @@ mod tests {
         assert_eq!(2, read_value(&bits, 30, 3));
     }
 
+    #[test]
+    fn test_decode_packet() {
+        assert_eq!(
+            (
+                49,
+                Packet {
+                    version: 1,
+                    packet_type: 6,
+                    contents: Contents::Operator(vec![
+                        Packet {
+                            version: 6,
+                            packet_type: 4,
+                            contents: Contents::Literal(10)
+                        },
+                        Packet {
+                            version: 2,
+                            packet_type: 4,
+                            contents: Contents::Literal(20)
+                        },
+                    ])
+                }
+            ),
+            decode_packet(load_packets("38006F45291200").unwrap().as_slice(), 0).unwrap()
+        );
+
+        assert_eq!(
+            "v6 sum = 3\n  v6 literal = 1\n  v2 literal = 2\n",
+            decode_packet(load_packets("C200B40A82").unwrap().as_slice(), 0)
+                .unwrap()
+                .1
+                .to_string()
+        );
+    }
+
     #[test]
     fn test_read_packet() {
         // simple literal
         assert_eq!(
             (21, 6, 2021),
-            read_packet(load_packets("D2FE28").unwrap().as_slice(), 0)
+            read_packet(load_packets("D2FE28").unwrap().as_slice(), 0).unwrap()
         );
 
         // versions sum correctly
         assert_eq!(
             1 + 6 + 2,
-            read_packet(load_packets("38006F45291200").unwrap().as_slice(), 0).1
+            read_packet(load_packets("38006F45291200").unwrap().as_slice(), 0)
+                .unwrap()
+                .1
         );
         assert_eq!(
             7 + 2 + 4 + 1,
-            read_packet(load_packets("EE00D40C823060").unwrap().as_slice(), 0).1
+            read_packet(load_packets("EE00D40C823060").unwrap().as_slice(), 0)
+                .unwrap()
+                .1
         );
         assert_eq!(
             4 + 1 + 5 + 6,
-            read_packet(load_packets("8A004A801A8002F478").unwrap().as_slice(), 0).1
+            read_packet(load_packets("8A004A801A8002F478").unwrap().as_slice(), 0)
+                .unwrap()
+                .1
         );
 
         // applies operations correctly
 
         assert_eq!(
             3,
-            read_packet(load_packets("C200B40A82").unwrap().as_slice(), 0).2
+            read_packet(load_packets("C200B40A82").unwrap().as_slice(), 0)
+                .unwrap()
+                .2
         );
         assert_eq!(
             54,
-            read_packet(load_packets("04005AC33890").unwrap().as_slice(), 0).2
+            read_packet(load_packets("04005AC33890").unwrap().as_slice(), 0)
+                .unwrap()
+                .2
         );
         assert_eq!(
             7,
-            read_packet(load_packets("880086C3E88112").unwrap().as_slice(), 0).2
+            read_packet(load_packets("880086C3E88112").unwrap().as_slice(), 0)
+                .unwrap()
+                .2
         );
         assert_eq!(
             9,
-            read_packet(load_packets("CE00C43D881120").unwrap().as_slice(), 0).2
+            read_packet(load_packets("CE00C43D881120").unwrap().as_slice(), 0)
+                .unwrap()
+                .2
         );
         assert_eq!(
             1,
-            read_packet(load_packets("D8005AC2A8F0").unwrap().as_slice(), 0).2
+            read_packet(load_packets("D8005AC2A8F0").unwrap().as_slice(), 0)
+                .unwrap()
+                .2
         );
         assert_eq!(
             0,
-            read_packet(load_packets("F600BC2D8F").unwrap().as_slice(), 0).2
+            read_packet(load_packets("F600BC2D8F").unwrap().as_slice(), 0)
+                .unwrap()
+                .2
         );
         assert_eq!(
             0,
-            read_packet(load_packets("9C005AC2F8F0").unwrap().as_slice(), 0).2
+            read_packet(load_packets("9C005AC2F8F0").unwrap().as_slice(), 0)
+                .unwrap()
+                .2
         );
         assert_eq!(
             1,
@@ -231,6 +522,7 @@ mod tests {
                     .as_slice(),
                 0
             )
+            .unwrap()
             .2
         );
     }
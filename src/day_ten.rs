@@ -1,15 +1,37 @@
+use alloc::vec::Vec;
+
 use anyhow::Result;
 
-pub const INPUT_PATH: &str = "inputs/day/10/input";
+use crate::checked::Overflow;
+use crate::metadata::{Metadata, Tag};
+
+pub const INPUT_PATH: &str = "inputs/2021/day/10/input";
+
+pub const METADATA: Metadata = Metadata {
+    title: "Syntax Scoring",
+    author: "medwards",
+    tags: &[Tag::Parsing],
+    examples: true,
+};
+
+pub fn part_one(contents: &str) -> Result<u64> {
+    part_one_lines(contents.lines().map(Ok))
+}
+
+pub fn part_two(contents: &str) -> Result<u64> {
+    part_two_lines(contents.lines().map(Ok))
+}
 
-pub fn part_one(contents: &str) -> Result<usize> {
-    let lines: Vec<_> = contents.lines().collect();
-    Ok(error_score(lines.as_slice()))
+pub fn part_one_lines<S: AsRef<str>>(mut lines: impl Iterator<Item = Result<S>>) -> Result<u64> {
+    lines.try_fold(0u64, |sum, line| {
+        Ok(sum
+            .checked_add(error_score(line?.as_ref()))
+            .ok_or(Overflow)?)
+    })
 }
 
-pub fn part_two(contents: &str) -> Result<usize> {
-    let lines: Vec<_> = contents.lines().collect();
-    Ok(completion_score(lines.as_slice()))
+pub fn part_two_lines<S: AsRef<str>>(lines: impl Iterator<Item = Result<S>>) -> Result<u64> {
+    completion_score(lines)
 }
 
 #[derive(Debug, PartialEq)]
@@ -18,92 +40,62 @@ enum Error {
     Unknown,
 }
 
-fn error_score(lines: &[&str]) -> usize {
-    check_delimiters(lines)
-        .iter()
-        .map(|res| match res {
-            Ok(_) => 0,
-            Err(Error::Unmatched(')')) => 3,
-            Err(Error::Unmatched(']')) => 57,
-            Err(Error::Unmatched('}')) => 1197,
-            Err(Error::Unmatched('>')) => 25137,
-            _ => panic!("Unexpected error"),
-        })
-        .sum()
+fn error_score(line: &str) -> u64 {
+    match check_delimiters(line) {
+        Ok(_) => 0,
+        Err(Error::Unmatched(')')) => 3,
+        Err(Error::Unmatched(']')) => 57,
+        Err(Error::Unmatched('}')) => 1197,
+        Err(Error::Unmatched('>')) => 25137,
+        _ => panic!("Unexpected error"),
+    }
 }
 
-fn completion_score(lines: &[&str]) -> usize {
-    let lines = check_delimiters(lines);
-
-    let lines: Vec<_> = lines
-        .iter()
-        .flatten() // throw away errors
-        .collect();
-    let mut scores: Vec<_> = autocomplete_lines(lines.as_slice())
-        .iter()
-        .map(|line| {
-            line.chars().fold(0, |score, character| {
-                (score * 5)
-                    + if character == ')' {
-                        1
-                    } else if character == ']' {
-                        2
-                    } else if character == '}' {
-                        3
-                    } else {
-                        4
-                    }
-            })
-        })
-        .collect();
+// only the score of each incomplete line is kept, not the line itself
+fn completion_score<S: AsRef<str>>(mut lines: impl Iterator<Item = Result<S>>) -> Result<u64> {
+    let mut scores = lines.try_fold(Vec::new(), |mut scores, line| -> Result<_> {
+        // throw away corrupted lines
+        if let Ok(opened) = check_delimiters(line?.as_ref()) {
+            // every delimiter left open multiplies the score by 5, past u64 after 27 of them
+            let score = opened.iter().rev().try_fold(0u64, |score, character| {
+                let points = match matching(*character) {
+                    ')' => 1,
+                    ']' => 2,
+                    '}' => 3,
+                    _ => 4,
+                };
+                score
+                    .checked_mul(5)
+                    .and_then(|score| score.checked_add(points))
+                    .ok_or(Overflow)
+            })?;
+            scores.push(score);
+        }
+        Ok(scores)
+    })?;
     scores.sort_unstable();
-    *scores.get(scores.len() / 2).unwrap()
-}
-
-// lines must be filtered to have no unmatched closing braces
-fn autocomplete_lines(lines: &[&&str]) -> Vec<String> {
-    lines
-        .iter()
-        .map(|line| {
-            let mut opened = line.chars().fold(Vec::new(), |mut opened, character| {
-                if "([{<".contains(character) {
-                    opened.push(character);
-                    opened
-                } else if opened.last().is_some() {
-                    opened.pop(); // we trust there are no unmatched braces
-                    opened
-                } else {
-                    opened
-                }
-            });
-            opened.reverse();
-
-            opened.iter().map(|c| matching(*c)).collect()
-        })
-        .collect()
+    scores
+        .get(scores.len() / 2)
+        .copied()
+        .ok_or_else(|| anyhow::anyhow!("no incomplete lines"))
 }
 
-fn check_delimiters<'a>(lines: &'a [&str]) -> Vec<std::result::Result<&'a str, Error>> {
-    lines
-        .iter()
-        .map(|line| {
-            let line_res = line.chars().try_fold(Vec::new(), |mut opened, character| {
-                if "([{<".contains(character) {
-                    opened.push(character);
-                } else if let Some(last) = opened.last() {
-                    if matching(*last) == character {
-                        opened.pop();
-                    } else {
-                        return Err(Error::Unmatched(character));
-                    }
-                } else {
-                    return Err(Error::Unknown);
-                }
-                Ok(opened)
-            });
-            line_res.map(|_| *line)
-        })
-        .collect()
+// returns the delimiters left open, innermost last
+fn check_delimiters(line: &str) -> core::result::Result<Vec<char>, Error> {
+    line.chars().try_fold(Vec::new(), |mut opened, character| {
+        if "([{<".contains(character) {
+            opened.push(character);
+        } else if let Some(last) = opened.last() {
+            if matching(*last) == character {
+                opened.pop();
+            } else {
+                return Err(Error::Unmatched(character));
+            }
+        } else {
+            return Err(Error::Unknown);
+        }
+        Ok(opened)
+    })
 }
 
 fn matching(open: char) -> char {
@@ -139,24 +131,13 @@ mod tests {
             "<{([{{}}[<[[[<>{}]]]>[]]",
         ];
 
-        assert_eq!(288957, completion_score(&input));
-    }
-
-    /*
-    #[test]
-    fn test_autocomplete_lines() {
-        let input = [
-            "[({(<(())[]>[[{[]{<()<>>",
-            "[(()[<>])]({[<{<<[]>>(",
-            "(((({<>}<{<{<>}{[]{[]{}",
-            "{<[[]]>}<{[{[{[]{()[[[]",
-            "<{([{{}}[<[[[<>{}]]]>[]]",
-        ];
+        assert_eq!(288957, completion_score(input.iter().map(Ok)).unwrap());
 
-        let expected = vec!["}}]])})]", ")}>]})", "}}>}>))))", "]]}}]}]}>", "])}>"];
-        assert_eq!(expected, autocomplete_lines(&input));
+        let unclosed = "<".repeat(28);
+        assert!(completion_score([unclosed.as_str()].iter().map(Ok))
+            .unwrap_err()
+            .is::<Overflow>());
     }
-    */
 
     #[test]
     fn test_check_delimiters() {
@@ -185,6 +166,10 @@ mod tests {
             Err(Error::Unmatched('>')),
             Ok("<{([{{}}[<[[[<>{}]]]>[]]"),
         ];
-        assert_eq!(expected, check_delimiters(&input));
+        let results: Vec<_> = input
+            .iter()
+            .map(|line| check_delimiters(line).map(|_| *line))
+            .collect();
+        assert_eq!(expected, results);
     }
 }
@@ -1,26 +1,60 @@
-use std::ops::ControlFlow;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+use core::ops::ControlFlow;
 
-use anyhow::{Error, Result};
+use anyhow::Result;
 
-pub const INPUT_PATH: &str = "inputs/day/13/input";
+use crate::checked::Overflow;
+use crate::diagnostic::Diagnostic;
+use crate::lint::Problem;
+use crate::metadata::{Metadata, Tag};
 
-pub fn part_one(contents: &str) -> Result<usize> {
+pub const INPUT_PATH: &str = "inputs/2021/day/13/input";
+
+pub const METADATA: Metadata = Metadata {
+    title: "Transparent Origami",
+    author: "medwards",
+    tags: &[Tag::Grid, Tag::Parsing],
+    examples: true,
+};
+
+pub fn part_one(contents: &str) -> Result<u64> {
     let (coords, folds) = load_paper(contents)?;
     Ok(folds
         .iter()
         .take(1)
-        .fold(coords, |coords, fold| fold_paper(coords.as_slice(), fold))
-        .len())
+        .try_fold(coords, |coords, fold| fold_paper(coords.as_slice(), fold))?
+        .len() as u64)
 }
 
-pub fn part_two(contents: &str) -> Result<usize> {
+pub fn part_two(contents: &str) -> Result<u64> {
     let (coords, folds) = load_paper(contents)?;
     let result = folds
         .iter()
-        .fold(coords, |coords, fold| fold_paper(coords.as_slice(), fold));
-    // Disabled for benchmarks
-    // println!("{:?}", result);
-    Ok(result.len())
+        .try_fold(coords, |coords, fold| fold_paper(coords.as_slice(), fold))?;
+    // the answer is the code spelled out by the dots
+    log::info!(target: "day13", "folded paper:\n{}", Paper(result.as_slice()));
+    Ok(result.len() as u64)
+}
+
+struct Paper<'a>(&'a [(usize, usize)]);
+
+impl fmt::Display for Paper<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.0.iter().map(|(x, _)| x + 1).max().unwrap_or(0);
+        let height = self.0.iter().map(|(_, y)| y + 1).max().unwrap_or(0);
+        (0..height).try_for_each(|y| {
+            (0..width).try_for_each(|x| {
+                let dot = if self.0.contains(&(x, y)) { '#' } else { '.' };
+                write!(f, "{}", dot)
+            })?;
+            writeln!(f)
+        })
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -29,88 +63,128 @@ pub enum Fold {
     Up(usize),
 }
 
-fn fold_paper(coords: &[(usize, usize)], fold: &Fold) -> Vec<(usize, usize)> {
-    let mut folded: Vec<_> = coords
+// dots further from the fold than the edge it folds onto would land off the paper
+fn fold_paper(
+    coords: &[(usize, usize)],
+    fold: &Fold,
+) -> core::result::Result<Vec<(usize, usize)>, Overflow> {
+    let mirror = |offset: usize, at: usize| offset.checked_sub(at - offset).ok_or(Overflow);
+    let mut folded = coords
         .iter()
         .map(|(x, y)| match fold {
             Fold::Right(offset) => {
                 if x > offset {
-                    (offset - (x - offset), *y)
+                    Ok((mirror(*offset, *x)?, *y))
                 } else {
-                    (*x, *y)
+                    Ok((*x, *y))
                 }
             }
             Fold::Up(offset) => {
                 if y > offset {
-                    (*x, offset - (y - offset))
+                    Ok((*x, mirror(*offset, *y)?))
                 } else {
-                    (*x, *y)
+                    Ok((*x, *y))
                 }
             }
         })
-        .collect();
+        .collect::<core::result::Result<Vec<_>, _>>()?;
     folded.sort_unstable();
     folded.dedup();
-    folded
+    log::debug!(target: "day13", "{:?} leaves {} dots", fold, folded.len());
+    Ok(folded)
 }
 
+const DOT_HINT: &str = "dots are written as x,y, ie 6,10";
+const FOLD_HINT: &str = "folds are written as 'fold along x=5' or 'fold along y=7'";
+
 fn load_paper(contents: &str) -> Result<(Vec<(usize, usize)>, Vec<Fold>)> {
-    let mut paper_iter = contents.lines();
-    let fold_res = paper_iter.try_fold(Vec::new(), |mut coords, line| match line.split_once(',') {
-        Some((x, y)) => {
-            let x: usize = match x.parse() {
-                Ok(x) => x,
-                Err(e) => return ControlFlow::Break(Err(e)),
-            };
-            let y: usize = match y.parse() {
-                Ok(y) => y,
-                Err(e) => return ControlFlow::Break(Err(e)),
-            };
-            coords.push((x, y));
-            ControlFlow::Continue(coords)
+    let diagnostic = |line: usize, column: usize, message: String, source: &str, hint| {
+        Diagnostic::new(
+            "load_paper",
+            Problem::new(line + 1, message).at(column),
+            source,
+        )
+        .hint(hint)
+    };
+    let mut paper_iter = contents.lines().enumerate();
+    let fold_res = paper_iter.try_fold(Vec::new(), |mut coords, (i, line)| {
+        match line.split_once(',') {
+            Some((x, y)) => {
+                let x: usize = match x.parse() {
+                    Ok(x) => x,
+                    Err(_) => {
+                        let message = format!("'{}' is not a coordinate", x);
+                        return ControlFlow::Break(Err(diagnostic(i, 1, message, line, DOT_HINT)));
+                    }
+                };
+                let y: usize = match y.parse() {
+                    Ok(y) => y,
+                    Err(_) => {
+                        let message = format!("'{}' is not a coordinate", y);
+                        let column = line.chars().count() - y.chars().count() + 1;
+                        return ControlFlow::Break(Err(diagnostic(
+                            i, column, message, line, DOT_HINT,
+                        )));
+                    }
+                };
+                coords.push((x, y));
+                ControlFlow::Continue(coords)
+            }
+            None => ControlFlow::Break(Ok(coords)),
         }
-        None => ControlFlow::Break(Ok(coords)),
     });
 
     // TODO: use break_value
     let coords = match fold_res {
-        ControlFlow::Continue(_) => panic!("unfinished coords iter"), // shouldn't happen
+        ControlFlow::Continue(_) => {
+            let last = contents.lines().last().unwrap_or("");
+            return Err(Diagnostic::new(
+                "load_paper",
+                Problem::new(
+                    contents.lines().count().max(1),
+                    "expected a blank line and folds after the dots".to_string(),
+                ),
+                last,
+            )
+            .hint(FOLD_HINT)
+            .into());
+        }
         ControlFlow::Break(res) => res?,
     };
 
-    let folds: Result<Vec<_>> = paper_iter
-        .map(|line| {
-            let parts = line.split_once('=');
-            // TODO: this awkwardness because `?` wasn't allowed inside the `map`. Why?
-            let parts = if let Some(parts) = parts {
-                parts
-            } else {
-                return Result::Err(anyhow::anyhow!("bad input"));
-            };
-
-            let fold_point: std::result::Result<usize, _> = parts.1.parse();
-            let fold_point = if let Ok(fold_point) = fold_point {
-                fold_point
-            } else {
-                return Result::Err(Error::new(fold_point.unwrap_err()));
-            };
+    let folds: core::result::Result<Vec<_>, Diagnostic> = paper_iter
+        .map(|(i, line)| {
+            let (axis, fold_point) = line.split_once('=').ok_or_else(|| {
+                diagnostic(i, 1, format!("'{}' is not a fold", line), line, FOLD_HINT)
+            })?;
+            let fold_point = fold_point.parse().map_err(|_| {
+                let message = format!("'{}' is not a fold line", fold_point);
+                diagnostic(i, axis.chars().count() + 2, message, line, FOLD_HINT)
+            })?;
 
-            Result::Ok(if parts.0.ends_with('x') {
-                Fold::Right(fold_point)
-            } else if parts.0.ends_with('y') {
-                Fold::Up(fold_point)
+            if axis.ends_with('x') {
+                Ok(Fold::Right(fold_point))
+            } else if axis.ends_with('y') {
+                Ok(Fold::Up(fold_point))
             } else {
-                return Result::Err(anyhow::anyhow!("bad input"));
-            })
+                let message = "folds are along x or y".to_string();
+                Err(diagnostic(
+                    i,
+                    axis.chars().count(),
+                    message,
+                    line,
+                    FOLD_HINT,
+                ))
+            }
         })
         .collect();
-    let folds = folds?;
 
-    Ok((coords, folds))
+    Ok((coords, folds?))
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::string::ToString;
     use std::fs::read_to_string;
 
     use super::*;
@@ -145,6 +219,34 @@ mod tests {
         assert_eq!(expected, load_paper(contents.as_str()).unwrap());
     }
 
+    #[test]
+    fn test_load_paper_diagnostics() {
+        let problem = |contents: &str| {
+            let error = load_paper(contents).unwrap_err();
+            let diagnostic = error.downcast_ref::<Diagnostic>().unwrap();
+            (diagnostic.problem.to_string(), diagnostic.source.clone())
+        };
+        assert_eq!(
+            (
+                "line 2, column 3: 'x' is not a coordinate".to_string(),
+                "6,x".to_string()
+            ),
+            problem("6,10\n6,x\n\nfold along y=7")
+        );
+        assert_eq!(
+            "line 3, column 12: folds are along x or y",
+            problem("6,10\n\nfold along z=7").0
+        );
+        assert_eq!(
+            "line 3, column 14: 'seven' is not a fold line",
+            problem("6,10\n\nfold along y=seven").0
+        );
+        assert_eq!(
+            "line 1: expected a blank line and folds after the dots",
+            problem("6,10").0
+        );
+    }
+
     #[test]
     fn test_fold_paper() {
         let input = vec![
@@ -168,6 +270,16 @@ mod tests {
             (9, 0),
         ];
 
-        assert_eq!(17, fold_paper(input.as_slice(), &Fold::Up(7)).len());
+        assert_eq!(
+            17,
+            fold_paper(input.as_slice(), &Fold::Up(7)).unwrap().len()
+        );
+        assert_eq!(Err(Overflow), fold_paper(&[(0, 15)], &Fold::Up(7)));
+    }
+
+    #[test]
+    fn test_paper_display() {
+        let paper = [(0, 0), (1, 0), (2, 0), (0, 1), (2, 2)];
+        assert_eq!("###\n#..\n..#\n", Paper(&paper).to_string());
     }
 }
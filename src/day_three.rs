@@ -1,41 +1,75 @@
-use std::{num::ParseIntError, ops::BitXor};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{num::ParseIntError, ops::BitXor};
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 
-use crate::read_to_lines;
+use crate::metadata::Metadata;
 
-pub const INPUT_PATH: &str = "inputs/day/3/input";
+pub const INPUT_PATH: &str = "inputs/2021/day/3/input";
 
-pub fn part_one(contents: &str) -> Result<usize> {
-    let diagnostics = read_to_lines(contents)?;
-    let diagnostic_length: u32 = diagnostics.get(0).map(|d| d.len() as u32).unwrap_or(0);
-    let gamma_rate = calculate_gamma_rate(diagnostics.as_slice(), diagnostic_length)?;
+pub const METADATA: Metadata = Metadata {
+    title: "Binary Diagnostic",
+    author: "medwards",
+    tags: &[],
+    examples: true,
+};
+
+pub fn part_one(contents: &str) -> Result<u64> {
+    part_one_lines(contents.lines().map(Ok))
+}
+
+pub fn part_two(contents: &str) -> Result<u64> {
+    part_two_lines(contents.lines().map(Ok))
+}
+
+pub fn part_one_lines<S: AsRef<str>>(lines: impl Iterator<Item = Result<S>>) -> Result<u64> {
+    let (ones, count) = count_ones(lines)?;
+    let diagnostic_length = ones.len() as u32;
+    let gamma_rate = gamma_rate(ones.as_slice(), count)?;
     let epsilon_rate = epsilon_rate_from_gamma_rate(gamma_rate, diagnostic_length)?;
-    Ok((gamma_rate * epsilon_rate) as usize)
+    Ok(u64::from(gamma_rate) * u64::from(epsilon_rate))
 }
 
-pub fn part_two(contents: &str) -> Result<usize> {
-    let diagnostics = read_to_lines(contents)?;
+// the ratings filter the diagnostics once per bit, so unlike part one these are kept in memory
+pub fn part_two_lines<S: AsRef<str>>(lines: impl Iterator<Item = Result<S>>) -> Result<u64> {
+    let diagnostics = lines
+        .map(|line| Ok(line?.as_ref().to_string()))
+        .collect::<Result<Vec<String>>>()?;
     let diagnostic_length: u32 = diagnostics.get(0).map(|d| d.len() as u32).unwrap_or(0);
     let generator_rating = calculate_generator_rating(diagnostics.as_slice(), diagnostic_length)?;
     let scrubber_rating = calculate_scrubber_rating(diagnostics.as_slice(), diagnostic_length)?;
-    Ok((generator_rating * scrubber_rating) as usize)
+    Ok(u64::from(generator_rating) * u64::from(scrubber_rating))
 }
 
-fn calculate_gamma_rate<T: AsRef<str>>(
-    diags: &[T],
-    diagnostic_length: u32,
-) -> Result<u32, ParseIntError> {
-    let rate: String = (0..diagnostic_length as usize)
-        .map(|i| {
-            if is_one_most_common(i, diags, true) {
-                "1"
-            } else {
-                "0"
-            }
-        })
-        .collect();
-    u32::from_str_radix(rate.as_str(), 2)
+// the number of ones at each position and the number of diagnostics
+fn count_ones<S: AsRef<str>>(
+    mut lines: impl Iterator<Item = Result<S>>,
+) -> Result<(Vec<usize>, usize)> {
+    lines.try_fold((Vec::new(), 0), |(mut ones, count), line| {
+        let line = line?;
+        let line = line.as_ref();
+        if ones.len() < line.len() {
+            ones.resize(line.len(), 0);
+        }
+        line.chars()
+            .zip(ones.iter_mut())
+            .filter(|(c, _)| *c == '1')
+            .for_each(|(_, ones)| *ones += 1);
+        Ok((ones, count + 1))
+    })
+}
+
+// ties count as a one, like `is_one_most_common` with a `true` bias
+fn gamma_rate(ones: &[usize], count: usize) -> Result<u32> {
+    if ones.len() > u32::BITS as usize {
+        return Err(anyhow!("diagnostics are longer than {} bits", u32::BITS));
+    }
+    Ok(ones
+        .iter()
+        .fold(0, |rate, ones| (rate << 1) | (2 * ones >= count) as u32))
 }
 
 fn is_one_most_common<T: AsRef<str>>(index: usize, diagnostics: &[T], bias: bool) -> bool {
@@ -135,8 +169,9 @@ mod tests {
     }
 
     #[test]
-    fn test_calculate_gamma_rate() {
-        assert_eq!(22, calculate_gamma_rate(EXAMPLE_INPUT, 5).unwrap());
+    fn test_gamma_rate() {
+        let (ones, count) = count_ones(EXAMPLE_INPUT.iter().map(Ok)).unwrap();
+        assert_eq!(22, gamma_rate(ones.as_slice(), count).unwrap());
     }
 
     #[test]
@@ -1,19 +1,50 @@
-use std::cmp::{max, min, Ordering};
-use std::collections::HashMap;
-use std::str::FromStr;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::cmp::{max, min, Ordering};
+use core::fmt;
+use core::str::FromStr;
 
 use anyhow::Result;
 
-pub const INPUT_PATH: &str = "inputs/day/12/input";
+use crate::collections::HashMap;
+use crate::metadata::{Metadata, Tag};
 
-pub fn part_one(contents: &str) -> Result<usize> {
+pub const INPUT_PATH: &str = "inputs/2021/day/12/input";
+
+pub const METADATA: Metadata = Metadata {
+    title: "Passage Pathing",
+    author: "medwards",
+    tags: &[Tag::Graph],
+    examples: true,
+};
+
+pub fn part_one(contents: &str) -> Result<u64> {
+    let edges = load_edges(contents)?;
+    Ok(find_paths(edges.as_slice()).len() as u64)
+}
+
+pub fn part_two(contents: &str) -> Result<u64> {
     let edges = load_edges(contents)?;
-    Ok(find_paths(edges.as_slice()).len())
+    Ok(find_paths_with_small(edges.as_slice()).len() as u64)
 }
 
-pub fn part_two(contents: &str) -> Result<usize> {
+pub fn inspect(contents: &str) -> Result<String> {
     let edges = load_edges(contents)?;
-    Ok(find_paths_with_small(edges.as_slice()).len())
+    let mut graph: Vec<_> = build_graph(edges.as_slice()).into_iter().collect();
+    graph.sort_unstable();
+    Ok(graph
+        .iter()
+        .map(|(vertex, neighbours)| {
+            let mut neighbours = neighbours.clone();
+            neighbours.sort_unstable();
+            let neighbours: Vec<_> = neighbours.iter().map(|v| v.to_string()).collect();
+            format!("{} -> {}\n", vertex, neighbours.join(", "))
+        })
+        .collect())
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
@@ -49,6 +80,29 @@ impl FromStr for Vertex {
     }
 }
 
+impl fmt::Display for Vertex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Vertex::Start => write!(f, "start"),
+            Vertex::End => write!(f, "end"),
+            Vertex::Cave(name, _) => write!(f, "{}", name),
+        }
+    }
+}
+
+struct Path<'a>(&'a [Vertex]);
+
+impl fmt::Display for Path<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().enumerate().try_for_each(|(i, v)| {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", v)
+        })
+    }
+}
+
 impl Ord for Vertex {
     fn cmp(&self, other: &Self) -> Ordering {
         use Ordering::*;
@@ -82,9 +136,8 @@ impl PartialOrd for Vertex {
     }
 }
 
-fn find_paths(edges: &[(Vertex, Vertex)]) -> Vec<Vec<Vertex>> {
+fn build_graph(edges: &[(Vertex, Vertex)]) -> HashMap<Vertex, Vec<Vertex>> {
     let mut graph: HashMap<Vertex, Vec<Vertex>> = HashMap::new();
-    let mut paths = Vec::new();
     edges
         .iter()
         .flat_map(|(v1, v2)| vec![(v1, v2), (v2, v1)])
@@ -92,6 +145,12 @@ fn find_paths(edges: &[(Vertex, Vertex)]) -> Vec<Vec<Vertex>> {
             let vertices = graph.entry(left.clone()).or_insert_with(Vec::new);
             vertices.push(right.clone())
         });
+    graph
+}
+
+fn find_paths(edges: &[(Vertex, Vertex)]) -> Vec<Vec<Vertex>> {
+    let graph = build_graph(edges);
+    let mut paths = Vec::new();
 
     let mut queue = vec![vec![Vertex::Start]];
 
@@ -101,6 +160,13 @@ fn find_paths(edges: &[(Vertex, Vertex)]) -> Vec<Vec<Vertex>> {
             .expect("queue always has a value per while condition");
         let v = path.iter().last().expect("empty path");
         if *v == Vertex::End {
+            log::trace!(
+                target: "day12",
+                "path {}: {}, {} queued",
+                paths.len() + 1,
+                Path(&path),
+                queue.len()
+            );
             paths.push(path);
             continue;
         }
@@ -118,19 +184,13 @@ fn find_paths(edges: &[(Vertex, Vertex)]) -> Vec<Vec<Vertex>> {
             });
     }
 
+    log::debug!(target: "day12", "{} paths", paths.len());
     paths
 }
 
 fn find_paths_with_small(edges: &[(Vertex, Vertex)]) -> Vec<Vec<Vertex>> {
-    let mut graph: HashMap<Vertex, Vec<Vertex>> = HashMap::new();
+    let graph = build_graph(edges);
     let mut paths = Vec::new();
-    edges
-        .iter()
-        .flat_map(|(v1, v2)| vec![(v1, v2), (v2, v1)])
-        .for_each(|(left, right)| {
-            let vertices = graph.entry(left.clone()).or_insert_with(Vec::new);
-            vertices.push(right.clone())
-        });
 
     // TODO: this can be simpler, just (Path, bool)
     // Then allow the caller to initialize the bool and replace find_paths
@@ -142,6 +202,16 @@ fn find_paths_with_small(edges: &[(Vertex, Vertex)]) -> Vec<Vec<Vertex>> {
             .expect("queue always has a value per while condition");
         let v = path.iter().last().expect("empty path");
         if *v == Vertex::End {
+            log::trace!(
+                target: "day12",
+                "path {}: {}{}, {} queued",
+                paths.len() + 1,
+                Path(&path),
+                used_small
+                    .as_ref()
+                    .map_or_else(String::new, |small| format!(" ({} twice)", small)),
+                queue.len()
+            );
             paths.push(path);
             continue;
         }
@@ -174,6 +244,7 @@ fn find_paths_with_small(edges: &[(Vertex, Vertex)]) -> Vec<Vec<Vertex>> {
             });
     }
 
+    log::debug!(target: "day12", "{} paths", paths.len());
     paths
 }
 
@@ -218,6 +289,15 @@ mod tests {
         )
     }
 
+    #[test]
+    fn test_inspect() {
+        let expected = "start -> A, b\nA -> start, b, c, end\nb -> start, A, d, end\nc -> A\nd -> b\nend -> A, b\n";
+        assert_eq!(
+            expected,
+            inspect(read_to_string("fixtures/simple_cave.txt").unwrap().as_str()).unwrap()
+        );
+    }
+
     #[test]
     fn test_find_paths() {
         let input = load_edges(read_to_string("fixtures/cave.txt").unwrap().as_str()).unwrap();
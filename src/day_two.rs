@@ -1,34 +1,51 @@
 use anyhow::{anyhow, Error, Result};
 
-pub const INPUT_PATH: &str = "inputs/day/2/input";
+use crate::checked::Overflow;
+use crate::metadata::{Metadata, Tag};
+use crate::process_results;
 
-pub fn part_one(contents: &str) -> Result<usize> {
-    let directions = read_to_directions(contents)?;
-    let (x, y) = calculate_position(directions.as_slice());
-    Ok(x * y)
+pub const INPUT_PATH: &str = "inputs/2021/day/2/input";
+
+pub const METADATA: Metadata = Metadata {
+    title: "Dive!",
+    author: "medwards",
+    tags: &[Tag::Simulation],
+    examples: true,
+};
+
+pub fn part_one(contents: &str) -> Result<u64> {
+    part_one_lines(contents.lines().map(Ok))
+}
+
+pub fn part_two(contents: &str) -> Result<u64> {
+    part_two_lines(contents.lines().map(Ok))
 }
 
-pub fn part_two(contents: &str) -> Result<usize> {
-    let directions = read_to_directions(contents)?;
-    let (x, y) = calculate_aimed_position(directions.as_slice());
-    Ok(x * y)
+pub fn part_one_lines<S: AsRef<str>>(lines: impl Iterator<Item = Result<S>>) -> Result<u64> {
+    let (x, y) = process_results(read_to_directions(lines), |d| calculate_position(d))??;
+    Ok(x.checked_mul(y).ok_or(Overflow)?)
 }
 
-// Assumes: depth cannot be less than 0
-#[derive(Debug, PartialEq)]
+pub fn part_two_lines<S: AsRef<str>>(lines: impl Iterator<Item = Result<S>>) -> Result<u64> {
+    let (x, y) = process_results(read_to_directions(lines), |d| calculate_aimed_position(d))??;
+    Ok(x.checked_mul(y).ok_or(Overflow)?)
+}
+
+// Rising above the surface fails as an overflow
+#[derive(Clone, Copy, Debug, PartialEq)]
 enum Direction {
-    Forward(usize),
-    Down(usize),
-    Up(usize),
+    Forward(u64),
+    Down(u64),
+    Up(u64),
 }
 
 impl TryFrom<&str> for Direction {
     type Error = Error;
-    fn try_from(value: &str) -> std::result::Result<Self, Self::Error> {
+    fn try_from(value: &str) -> core::result::Result<Self, Self::Error> {
         let (dir, delta) = value
             .split_once(" ")
             .ok_or_else(|| anyhow!("Malformed input"))?;
-        let delta: usize = delta.parse()?;
+        let delta: u64 = delta.parse()?;
         match dir {
             "forward" => Ok(Direction::Forward(delta)),
             "up" => Ok(Direction::Up(delta)),
@@ -38,40 +55,58 @@ impl TryFrom<&str> for Direction {
     }
 }
 
-fn calculate_position(directions: &[Direction]) -> (usize, usize) {
+fn calculate_position(
+    mut directions: impl Iterator<Item = Direction>,
+) -> core::result::Result<(u64, u64), Overflow> {
     use Direction::*;
-    directions
-        .iter()
-        .fold((0, 0), |(x, y), direction| match direction {
-            Forward(delta) => (x + delta, y),
-            Down(delta) => (x, y + delta),
-            Up(delta) => (x, y - delta),
-        })
+    directions.try_fold((0u64, 0u64), |(x, y), direction| match direction {
+        Forward(delta) => Ok((x.checked_add(delta).ok_or(Overflow)?, y)),
+        Down(delta) => Ok((x, y.checked_add(delta).ok_or(Overflow)?)),
+        Up(delta) => Ok((x, y.checked_sub(delta).ok_or(Overflow)?)),
+    })
 }
 
-fn calculate_aimed_position(directions: &[Direction]) -> (usize, usize) {
+fn calculate_aimed_position(
+    mut directions: impl Iterator<Item = Direction>,
+) -> core::result::Result<(u64, u64), Overflow> {
     use Direction::*;
     let aimed_position =
-        directions
-            .iter()
-            .fold((0, 0, 0), |(x, y, aim), direction| match direction {
-                Forward(delta) => (x + delta, y + (aim * delta), aim),
-                Down(delta) => (x, y, aim + delta),
-                Up(delta) => (x, y, aim - delta),
-            });
-    (aimed_position.0, aimed_position.1)
+        directions.try_fold(
+            (0u64, 0u64, 0u64),
+            |(x, y, aim), direction| match direction {
+                Forward(delta) => {
+                    let depth = aim
+                        .checked_mul(delta)
+                        .and_then(|depth| y.checked_add(depth));
+                    Ok((
+                        x.checked_add(delta).ok_or(Overflow)?,
+                        depth.ok_or(Overflow)?,
+                        aim,
+                    ))
+                }
+                Down(delta) => Ok((x, y, aim.checked_add(delta).ok_or(Overflow)?)),
+                Up(delta) => Ok((x, y, aim.checked_sub(delta).ok_or(Overflow)?)),
+            },
+        )?;
+    Ok((aimed_position.0, aimed_position.1))
 }
 
-fn read_to_directions(contents: &str) -> Result<Vec<Direction>> {
-    let directions: Result<Vec<_>> = contents.lines().map(Direction::try_from).collect();
-    directions
+fn read_to_directions<S: AsRef<str>>(
+    lines: impl Iterator<Item = Result<S>>,
+) -> impl Iterator<Item = Result<Direction>> {
+    lines.map(|line| Direction::try_from(line?.as_ref()))
 }
 
 #[cfg(test)]
 mod tests {
+    use alloc::vec::Vec;
     use std::fs::read_to_string;
 
-    use super::{calculate_aimed_position, calculate_position, read_to_directions, Direction};
+    use anyhow::Result;
+
+    use super::{
+        calculate_aimed_position, calculate_position, read_to_directions, Direction, Overflow,
+    };
 
     const EXAMPLE_INPUT: &'static [Direction] = &[
         Direction::Forward(5),
@@ -84,12 +119,28 @@ mod tests {
 
     #[test]
     fn test_calculate_position() {
-        assert_eq!((15, 10), calculate_position(EXAMPLE_INPUT));
+        assert_eq!(
+            Ok((15, 10)),
+            calculate_position(EXAMPLE_INPUT.iter().copied())
+        );
+        assert_eq!(
+            Err(Overflow),
+            calculate_position([Direction::Down(2), Direction::Up(3)].into_iter())
+        );
     }
 
     #[test]
     fn test_calculate_aimed_position() {
-        assert_eq!((15, 60), calculate_aimed_position(EXAMPLE_INPUT));
+        assert_eq!(
+            Ok((15, 60)),
+            calculate_aimed_position(EXAMPLE_INPUT.iter().copied())
+        );
+        assert_eq!(
+            Err(Overflow),
+            calculate_aimed_position(
+                [Direction::Down(u64::MAX), Direction::Forward(2)].into_iter()
+            )
+        );
     }
 
     #[test]
@@ -99,8 +150,10 @@ mod tests {
             read_to_directions(
                 read_to_string("fixtures/submarine_directions.txt")
                     .unwrap()
-                    .as_str()
+                    .lines()
+                    .map(Ok)
             )
+            .collect::<Result<Vec<_>>>()
             .unwrap()
             .as_slice()
         );
@@ -0,0 +1,123 @@
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::fmt;
+
+use crate::lint::Problem;
+
+/// An error of a loader locating what it couldn't read in its input, with the offending line so
+/// it can be rendered without the rest of the input
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    /// Name of the function that failed, ie `load_paper`
+    pub loader: &'static str,
+    pub problem: Problem,
+    pub source: String,
+    pub hint: Option<&'static str>,
+}
+
+impl Diagnostic {
+    pub fn new(loader: &'static str, problem: Problem, source: &str) -> Diagnostic {
+        Diagnostic {
+            loader,
+            problem,
+            source: source.to_string(),
+            hint: None,
+        }
+    }
+
+    pub fn hint(self, hint: &'static str) -> Diagnostic {
+        Diagnostic {
+            hint: Some(hint),
+            ..self
+        }
+    }
+
+    /// Renders like a compiler error, the offending line with a caret under the failing column:
+    ///
+    /// ```text
+    /// error: 'x' is not a coordinate
+    ///  --> day 13, load_paper, line 3, column 3
+    ///   |
+    /// 3 | 6,x
+    ///   |   ^
+    ///   = hint: dots are written as x,y, ie 6,10
+    /// ```
+    pub fn render(&self, day: &str) -> String {
+        let Problem {
+            line,
+            column,
+            message,
+        } = &self.problem;
+        let gutter = " ".repeat(line.to_string().len());
+        let mut rendered = format!("error: {}\n", message);
+        rendered.push_str(&format!(
+            "{}--> day {}, {}, line {}",
+            gutter, day, self.loader, line
+        ));
+        if let Some(column) = column {
+            rendered.push_str(&format!(", column {}", column));
+        }
+        rendered.push_str(&format!("\n{} |\n{} | {}\n", gutter, line, self.source));
+        if let Some(column) = column {
+            rendered.push_str(&format!(
+                "{} | {}^\n",
+                gutter,
+                " ".repeat(column.saturating_sub(1))
+            ));
+        }
+        if let Some(hint) = self.hint {
+            rendered.push_str(&format!("{} = hint: {}\n", gutter, hint));
+        }
+        rendered
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.problem.fmt(f)
+    }
+}
+
+impl core::error::Error for Diagnostic {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() {
+        let diagnostic = Diagnostic::new(
+            "load_paper",
+            Problem::new(3, "'x' is not a coordinate".to_string()).at(3),
+            "6,x",
+        )
+        .hint("dots are written as x,y, ie 6,10");
+        assert_eq!(
+            "line 3, column 3: 'x' is not a coordinate",
+            diagnostic.to_string()
+        );
+        assert_eq!(
+            "\
+error: 'x' is not a coordinate
+ --> day 13, load_paper, line 3, column 3
+  |
+3 | 6,x
+  |   ^
+  = hint: dots are written as x,y, ie 6,10
+",
+            diagnostic.render("13")
+        );
+
+        let diagnostic = Diagnostic::new(
+            "load_polymerization",
+            Problem::new(12, "empty input".to_string()),
+            "",
+        );
+        assert_eq!(
+            "error: empty input\n  --> day 14, load_polymerization, line 12\n   |\n12 | \n",
+            diagnostic.render("14")
+        );
+    }
+}
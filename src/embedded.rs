@@ -0,0 +1,147 @@
+use std::path::Path;
+
+/// Puzzle inputs and fixtures compiled into the crate, by their path relative to the crate root
+pub const FILES: &[(&str, &str)] = &[
+    #[cfg(feature = "day1")]
+    (
+        "inputs/2021/day/1/input",
+        include_str!("../inputs/2021/day/1/input"),
+    ),
+    #[cfg(feature = "day2")]
+    (
+        "inputs/2021/day/2/input",
+        include_str!("../inputs/2021/day/2/input"),
+    ),
+    #[cfg(feature = "day3")]
+    (
+        "inputs/2021/day/3/input",
+        include_str!("../inputs/2021/day/3/input"),
+    ),
+    #[cfg(feature = "day4")]
+    (
+        "inputs/2021/day/4/input",
+        include_str!("../inputs/2021/day/4/input"),
+    ),
+    #[cfg(feature = "day5")]
+    (
+        "inputs/2021/day/5/input",
+        include_str!("../inputs/2021/day/5/input"),
+    ),
+    #[cfg(feature = "day6")]
+    (
+        "inputs/2021/day/6/input",
+        include_str!("../inputs/2021/day/6/input"),
+    ),
+    #[cfg(feature = "day7")]
+    (
+        "inputs/2021/day/7/input",
+        include_str!("../inputs/2021/day/7/input"),
+    ),
+    #[cfg(feature = "day8")]
+    (
+        "inputs/2021/day/8/input",
+        include_str!("../inputs/2021/day/8/input"),
+    ),
+    #[cfg(feature = "day9")]
+    (
+        "inputs/2021/day/9/input",
+        include_str!("../inputs/2021/day/9/input"),
+    ),
+    #[cfg(feature = "day10")]
+    (
+        "inputs/2021/day/10/input",
+        include_str!("../inputs/2021/day/10/input"),
+    ),
+    #[cfg(feature = "day11")]
+    (
+        "inputs/2021/day/11/input",
+        include_str!("../inputs/2021/day/11/input"),
+    ),
+    #[cfg(feature = "day12")]
+    (
+        "inputs/2021/day/12/input",
+        include_str!("../inputs/2021/day/12/input"),
+    ),
+    #[cfg(feature = "day13")]
+    (
+        "inputs/2021/day/13/input",
+        include_str!("../inputs/2021/day/13/input"),
+    ),
+    #[cfg(feature = "day14")]
+    (
+        "inputs/2021/day/14/input",
+        include_str!("../inputs/2021/day/14/input"),
+    ),
+    #[cfg(feature = "day15")]
+    (
+        "inputs/2021/day/15/input",
+        include_str!("../inputs/2021/day/15/input"),
+    ),
+    #[cfg(feature = "day16")]
+    (
+        "inputs/2021/day/16/input",
+        include_str!("../inputs/2021/day/16/input"),
+    ),
+    ("fixtures/bingo.txt", include_str!("../fixtures/bingo.txt")),
+    ("fixtures/cave.txt", include_str!("../fixtures/cave.txt")),
+    (
+        "fixtures/digits.txt",
+        include_str!("../fixtures/digits.txt"),
+    ),
+    ("fixtures/grid.txt", include_str!("../fixtures/grid.txt")),
+    (
+        "fixtures/grid_tiled.txt",
+        include_str!("../fixtures/grid_tiled.txt"),
+    ),
+    (
+        "fixtures/heightmap.txt",
+        include_str!("../fixtures/heightmap.txt"),
+    ),
+    (
+        "fixtures/manual_page.txt",
+        include_str!("../fixtures/manual_page.txt"),
+    ),
+    (
+        "fixtures/polymer.txt",
+        include_str!("../fixtures/polymer.txt"),
+    ),
+    (
+        "fixtures/positive_integers.txt",
+        include_str!("../fixtures/positive_integers.txt"),
+    ),
+    (
+        "fixtures/simple_cave.txt",
+        include_str!("../fixtures/simple_cave.txt"),
+    ),
+    (
+        "fixtures/submarine_directions.txt",
+        include_str!("../fixtures/submarine_directions.txt"),
+    ),
+    ("fixtures/vents.txt", include_str!("../fixtures/vents.txt")),
+];
+
+/// The embedded copy of `path`, which can be a day's input moved under another inputs directory
+/// (ie `/srv/advent/2021/day/6/input`)
+pub fn find(path: &Path) -> Option<&'static str> {
+    FILES.iter().find_map(|(embedded, contents)| {
+        let relative = embedded.strip_prefix("inputs/").unwrap_or(embedded);
+        path.ends_with(relative).then_some(*contents)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find() {
+        assert_eq!(
+            Some(include_str!("../inputs/2021/day/6/input")),
+            find(Path::new("inputs/2021/day/6/input"))
+        );
+        assert!(find(Path::new("/srv/advent/2021/day/6/input")).is_some());
+        assert!(find(Path::new("fixtures/bingo.txt")).is_some());
+        assert!(find(Path::new("inputs/2021/day/6/alice")).is_none());
+        assert!(find(Path::new("6/input")).is_none());
+    }
+}
@@ -0,0 +1,221 @@
+use std::fs::{read_to_string, write};
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+
+use crate::inputs::answers_path;
+
+/// What a saved puzzle page shows: the day it describes, the code blocks of part one that could be
+/// its example input and the answers of the example, one per part
+#[derive(Debug, PartialEq)]
+struct Page {
+    day: Option<u8>,
+    blocks: Vec<String>,
+    answers: Vec<Option<u64>>,
+}
+
+/// Extracts the example input and answers of `day` from a puzzle page saved as HTML and writes
+/// them to `fixtures/dayN_example.txt` and its answers file. Asks on stdin which block is the
+/// input when part one shows several.
+pub fn run(number: u8, page_path: &Path, force: bool) -> Result<()> {
+    let html = read_to_string(page_path)
+        .map_err(|e| anyhow!("Unable to read from {} - {}", page_path.display(), e))?;
+    let page = parse(&html);
+    if let Some(shown) = page.day.filter(|&shown| shown != number) {
+        return Err(anyhow!(
+            "{} is the page of day {}, not day {}",
+            page_path.display(),
+            shown,
+            number
+        ));
+    }
+    let fixture = PathBuf::from(format!("fixtures/day{}_example.txt", number));
+    if fixture.exists() && !force {
+        return Err(anyhow!(
+            "{} already exists, pass --force to replace it",
+            fixture.display()
+        ));
+    }
+    let stdin = io::stdin();
+    let chosen = choose(&page.blocks, stdin.lock(), io::stderr())?;
+    let example = &page.blocks[chosen];
+    write(&fixture, example)
+        .map_err(|e| anyhow!("Unable to write to {} - {}", fixture.display(), e))?;
+    println!(
+        "Wrote {} ({} lines)",
+        fixture.display(),
+        example.lines().count()
+    );
+
+    if page.answers.iter().all(Option::is_none) {
+        println!("{} shows no answers", page_path.display());
+        return Ok(());
+    }
+    let answers_path = answers_path(&fixture);
+    let answers: String = page
+        .answers
+        .iter()
+        .map(|answer| answer.map_or_else(String::new, |answer| answer.to_string()) + "\n")
+        .collect();
+    write(&answers_path, &answers)
+        .map_err(|e| anyhow!("Unable to write to {} - {}", answers_path.display(), e))?;
+    println!(
+        "Wrote {} ({})",
+        answers_path.display(),
+        answers.lines().collect::<Vec<_>>().join(", ")
+    );
+    Ok(())
+}
+
+/// Reads the articles of a puzzle page, one per part. Blocks marked up with emphasis illustrate
+/// the puzzle rather than being an input, and the answer of a part is the last number it
+/// emphasizes outside of its blocks.
+fn parse(html: &str) -> Page {
+    let articles: Vec<_> = html
+        .split("<article")
+        .skip(1)
+        .map(|article| article.split("</article>").next().unwrap_or(article))
+        .collect();
+    let day = articles.first().and_then(|article| {
+        let title = article.split("--- Day ").nth(1)?;
+        title.split(':').next()?.trim().parse().ok()
+    });
+    let blocks = articles.first().map_or_else(Vec::new, |article| {
+        between(article, "<pre><code>", "</code></pre>")
+            .filter(|block| !block.contains('<'))
+            .map(unescape)
+            .collect()
+    });
+    let answers = articles
+        .iter()
+        .map(|article| {
+            let prose: String = article
+                .split("<pre>")
+                .map(|part| part.split_once("</pre>").map_or(part, |(_, after)| after))
+                .collect();
+            between(&prose, "<em>", "</em>")
+                .filter_map(|emphasized| emphasized.trim().parse().ok())
+                .last()
+        })
+        .collect();
+    Page {
+        day,
+        blocks,
+        answers,
+    }
+}
+
+// The text of every `start` ... `end` span, ie the contents of each element of a kind
+fn between<'a>(text: &'a str, start: &'a str, end: &'a str) -> impl Iterator<Item = &'a str> {
+    text.split(start)
+        .skip(1)
+        .filter_map(move |rest| rest.split_once(end).map(|(inside, _)| inside))
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/// Picks the block holding the example input, asking which one when there are several
+fn choose(blocks: &[String], mut input: impl BufRead, mut output: impl Write) -> Result<usize> {
+    match blocks.len() {
+        0 => return Err(anyhow!("part one shows no example input")),
+        1 => return Ok(0),
+        _ => {}
+    }
+    writeln!(
+        output,
+        "Part one shows {} blocks that could be the example input:",
+        blocks.len()
+    )?;
+    for (index, block) in blocks.iter().enumerate() {
+        let first = block.lines().next().unwrap_or_default();
+        writeln!(
+            output,
+            "  {}. {} ({} lines)",
+            index + 1,
+            first,
+            block.lines().count()
+        )?;
+    }
+    loop {
+        write!(output, "Which one is the input? [1-{}] ", blocks.len())?;
+        output.flush()?;
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            return Err(anyhow!("no block was chosen"));
+        }
+        match line.trim().parse::<usize>() {
+            Ok(chosen) if (1..=blocks.len()).contains(&chosen) => return Ok(chosen - 1),
+            _ => writeln!(output, "'{}' is not one of the blocks", line.trim())?,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 10: Syntax Scoring ---</h2>
+<p>For example, consider the following navigation subsystem:</p>
+<pre><code>[({(&lt;(())[]&gt;[[{[]{&lt;()&lt;&gt;&gt;
+[(()[&lt;&gt;])]({[&lt;{&lt;&lt;[]&gt;&gt;(
+</code></pre>
+<p>Some of the lines aren't corrupted:</p>
+<pre><code>{([(&lt;{}[&lt;&gt;[]}&gt;{[]{[(&lt;()&gt; - Expected ], but found <em>}</em> instead.
+</code></pre>
+<p>The total syntax error score is <code><em>26397</em></code> points.</p>
+</article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code><em>288957</em> total points.
+</code></pre>
+<p>The middle score is <code><em>288957</em></code> points.</p>
+</article>
+<p>Your puzzle answer was <code>392097</code>.</p>
+</main>"#;
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            Page {
+                day: Some(10),
+                blocks: vec!["[({(<(())[]>[[{[]{<()<>>\n[(()[<>])]({[<{<<[]>>(\n".to_string()],
+                answers: vec![Some(26397), Some(288957)],
+            },
+            parse(PAGE)
+        );
+        let part_one = PAGE
+            .split("<article")
+            .take(2)
+            .collect::<Vec<_>>()
+            .join("<article");
+        assert_eq!(vec![Some(26397)], parse(&part_one).answers);
+        assert_eq!(None, parse("<p>Not a puzzle</p>").day);
+    }
+
+    #[test]
+    fn test_choose() {
+        let blocks = vec!["start-A\nA-end\n".to_string(), "dc-end\n".to_string()];
+        let mut output = Vec::new();
+        assert_eq!(
+            1,
+            choose(&blocks, "3\n2\n".as_bytes(), &mut output).unwrap()
+        );
+        assert_eq!(
+            "Part one shows 2 blocks that could be the example input:\n  \
+             1. start-A (2 lines)\n  2. dc-end (1 lines)\n\
+             Which one is the input? [1-2] '3' is not one of the blocks\n\
+             Which one is the input? [1-2] ",
+            String::from_utf8(output).unwrap()
+        );
+        assert!(choose(&blocks, "".as_bytes(), Vec::new()).is_err());
+        assert_eq!(0, choose(&blocks[..1], "".as_bytes(), Vec::new()).unwrap());
+        assert!(choose(&[], "".as_bytes(), Vec::new()).is_err());
+    }
+}
@@ -0,0 +1,117 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::thread;
+
+use anyhow::{anyhow, Result};
+
+/// A solver in another language. Its command line is run once per part with the part (`1` or `2`)
+/// appended and the input on stdin. It answers with a line `answer N` or fails with a line
+/// `error MESSAGE` on stdout, any other line is ignored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct External {
+    program: String,
+    args: Vec<String>,
+}
+
+impl External {
+    /// Splits `command` on whitespace into a program and its arguments, no shell is involved
+    pub fn new(command: &str) -> Result<External> {
+        let mut words = command.split_whitespace().map(str::to_string);
+        let program = words.next().ok_or_else(|| anyhow!("empty command"))?;
+        Ok(External {
+            program,
+            args: words.collect(),
+        })
+    }
+
+    pub fn solve(&self, part: usize, contents: &str) -> Result<u64> {
+        let mut child = Command::new(&self.program)
+            .args(&self.args)
+            .arg(part.to_string())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| anyhow!("Unable to run {} - {}", self.program, e))?;
+
+        // written concurrently so a solver answering before reading all of its input can't block
+        let mut stdin = child.stdin.take().expect("stdin is piped");
+        let contents = contents.to_string();
+        let writer = thread::spawn(move || stdin.write_all(contents.as_bytes()));
+        let output = child.wait_with_output()?;
+        let _ = writer.join();
+
+        match answer(String::from_utf8_lossy(&output.stdout).as_ref()) {
+            Some(answer) => answer,
+            None if output.status.success() => Err(anyhow!("{} printed no answer", self.program)),
+            None => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                Err(anyhow!(
+                    "{} failed with {} - {}",
+                    self.program,
+                    output.status,
+                    stderr.lines().last().unwrap_or("")
+                ))
+            }
+        }
+    }
+}
+
+fn answer(stdout: &str) -> Option<Result<u64>> {
+    stdout.lines().find_map(|line| {
+        if let Some(answer) = line.strip_prefix("answer ") {
+            Some(
+                answer
+                    .trim()
+                    .parse()
+                    .map_err(|_| anyhow!("'{}' is not an answer", answer.trim())),
+            )
+        } else {
+            line.strip_prefix("error ")
+                .map(|message| Err(anyhow!("{}", message.trim())))
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(
+            5934,
+            answer("parsed 5 fish\nanswer 5934\n").unwrap().unwrap()
+        );
+        assert_eq!(
+            "no fish",
+            answer("error no fish\nanswer 1")
+                .unwrap()
+                .unwrap_err()
+                .to_string()
+        );
+        assert!(answer("answer many").unwrap().is_err());
+        assert!(answer("5934").is_none());
+        assert!(External::new("  ").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_solve() {
+        let script =
+            std::env::temp_dir().join(format!("advent2021-{}-test-solve.sh", std::process::id()));
+        std::fs::write(
+            &script,
+            "read count\necho \"counted $count\"\necho \"answer $((count * $1))\"\n",
+        )
+        .unwrap();
+        let external = External::new(format!("sh {}", script.display()).as_str()).unwrap();
+        assert_eq!(21, external.solve(1, "21\n").unwrap());
+        assert_eq!(42, external.solve(2, "21\n").unwrap());
+        assert!(External::new("advent2021-no-such-program")
+            .unwrap()
+            .solve(1, "")
+            .is_err());
+        std::fs::remove_file(&script).unwrap();
+    }
+}
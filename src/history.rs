@@ -0,0 +1,75 @@
+use std::collections::BTreeMap;
+use std::fs::{create_dir_all, read_to_string, OpenOptions};
+use std::io::{self, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Appends the answer of a part of a day's puzzle input to the run history at `path`, one line
+/// `SECONDS DAY PART ANSWER` per answer with the seconds since the epoch it was given at
+pub fn record(path: &Path, day: &str, part: usize, answer: u64) -> io::Result<()> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(io::Error::other)?;
+    if let Some(directory) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        create_dir_all(directory)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{} {} {} {}", now.as_secs(), day, part, answer)
+}
+
+/// When each part of each day was first answered, by day and part. Lines that can't be read are
+/// skipped and a missing history is an empty one.
+pub fn first_answers(path: &Path) -> io::Result<BTreeMap<(String, usize), u64>> {
+    let contents = match read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(BTreeMap::new()),
+        Err(e) => return Err(e),
+    };
+    let mut first = BTreeMap::new();
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut words = line.split_whitespace();
+            let seconds = words.next()?.parse::<u64>().ok()?;
+            let day = words.next()?;
+            let part = words.next()?.parse::<usize>().ok()?;
+            Some(((day.to_string(), part), seconds))
+        })
+        .for_each(|(key, seconds)| {
+            first
+                .entry(key)
+                .and_modify(|first: &mut u64| *first = seconds.min(*first))
+                .or_insert(seconds);
+        });
+    Ok(first)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::fs::{remove_file, write};
+
+    use super::*;
+
+    #[test]
+    fn test_first_answers() {
+        let path = temp_dir().join(format!(
+            "advent2021-{}-test-first-answers",
+            std::process::id()
+        ));
+        assert!(first_answers(&path).unwrap().is_empty());
+
+        write(
+            &path,
+            "300 6 1 5934\n200 6 1 5934\nnot a line\n100 jhorstmann::6 2 26984457539\n",
+        )
+        .unwrap();
+        record(&path, "6", 2, 26984457539).unwrap();
+        let first = first_answers(&path).unwrap();
+        assert_eq!(Some(&200), first.get(&("6".to_string(), 1)));
+        assert_eq!(Some(&100), first.get(&("jhorstmann::6".to_string(), 2)));
+        assert!(first[&("6".to_string(), 2)] > 300);
+        assert_eq!(3, first.len());
+        remove_file(&path).unwrap();
+    }
+}
@@ -0,0 +1,90 @@
+use std::fs::read_dir;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Result};
+
+/// Which of the inputs next to a day's `INPUT_PATH` to solve
+pub enum Selection<'a> {
+    Default,
+    Named(&'a str),
+    All,
+}
+
+/// Expected answers of an input are stored next to it, ie `inputs/2021/day/5/alice.answers`
+const ANSWERS_EXTENSION: &str = ".answers";
+
+/// The file holding the expected answers of an input, one line per part
+pub fn answers_path(input_path: &Path) -> PathBuf {
+    let mut path = input_path.as_os_str().to_os_string();
+    path.push(ANSWERS_EXTENSION);
+    PathBuf::from(path)
+}
+
+/// Resolves the selected inputs to their paths, named unless the default input was selected.
+/// The named inputs of a day are the files in the directory of its input, ie
+/// `inputs/2021/day/5/alice` and `inputs/2021/day/5/bob` with `input` being the default one.
+pub fn select(input_path: &Path, selection: &Selection) -> Result<Vec<(Option<String>, PathBuf)>> {
+    let directory = input_path.parent().unwrap_or_else(|| Path::new(""));
+    match selection {
+        Selection::Default => Ok(vec![(None, input_path.to_path_buf())]),
+        Selection::Named(name) => Ok(vec![(Some(name.to_string()), directory.join(name))]),
+        Selection::All => {
+            let mut inputs = read_dir(directory)
+                .map_err(|e| anyhow!("Unable to list {} - {}", directory.display(), e))?
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_file())
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    // skips editor and OS droppings like .input.swp or .DS_Store
+                    let input = !name.starts_with('.') && !name.ends_with(ANSWERS_EXTENSION);
+                    input.then(|| (Some(name), entry.path()))
+                })
+                .collect::<Vec<_>>();
+            if inputs.is_empty() {
+                return Err(anyhow!("{} has no inputs", directory.display()));
+            }
+            inputs.sort();
+            Ok(inputs)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env::temp_dir;
+    use std::fs::{create_dir_all, remove_dir_all, write};
+
+    use super::*;
+
+    #[test]
+    fn test_select() {
+        // unique to the run, so leftovers of others and parallel runs can't interfere
+        let root = temp_dir().join(format!("advent2021-{}-test-select", std::process::id()));
+        let directory = root.join("day/5");
+        create_dir_all(&directory).unwrap();
+        ["input", "bob", "alice", ".input.swp", "bob.answers"]
+            .iter()
+            .for_each(|name| write(directory.join(name), "").unwrap());
+        let input_path = directory.join("input");
+
+        assert_eq!(
+            vec![(None, input_path.clone())],
+            select(&input_path, &Selection::Default).unwrap()
+        );
+        assert_eq!(
+            vec![(Some("bob".to_string()), directory.join("bob"))],
+            select(&input_path, &Selection::Named("bob")).unwrap()
+        );
+        let names: Vec<_> = select(&input_path, &Selection::All)
+            .unwrap()
+            .into_iter()
+            .map(|(name, _)| name.unwrap())
+            .collect();
+        assert_eq!(vec!["alice", "bob", "input"], names);
+        assert_eq!(
+            directory.join("bob.answers"),
+            answers_path(&directory.join("bob"))
+        );
+        remove_dir_all(&root).unwrap();
+    }
+}
@@ -0,0 +1,341 @@
+use std::collections::BTreeMap;
+use std::fs::read_to_string;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use advent2021::config::Config;
+use advent2021::DEFAULT_YEAR;
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+
+use crate::history;
+
+/// A private leaderboard as exported by adventofcode.com
+#[derive(Debug, Deserialize)]
+struct Leaderboard {
+    event: String,
+    members: BTreeMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Member {
+    id: u64,
+    name: Option<String>,
+    local_score: u64,
+    stars: u64,
+    /// When each star was earned, by day and part
+    #[serde(default)]
+    completion_day_level: BTreeMap<u8, BTreeMap<usize, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Star {
+    get_star_ts: u64,
+}
+
+impl Member {
+    fn name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+/// Prints the standings of the leaderboard exported to `path`, or fetched from the configured URL
+/// without one, then how long after unlocking each member solved each part of each day next to
+/// how long it took to first answer it locally according to the run history
+pub fn run(config: &Config, path: Option<&str>, out: &mut impl Write) -> Result<()> {
+    let json = match (path, config.leaderboard()) {
+        (Some(path), _) => {
+            read_to_string(path).map_err(|e| anyhow!("Unable to read from {} - {}", path, e))?
+        }
+        (None, Some(url)) => fetch(url, config.session())?,
+        (None, None) => return Err(anyhow!("no leaderboard file given nor URL configured")),
+    };
+    let history = config.history();
+    let local = history::first_answers(&history)
+        .map_err(|e| anyhow!("Unable to read from {} - {}", history.display(), e))?;
+    write(&json, &local, out)
+}
+
+// curl handles TLS, the session token is passed on stdin to keep it out of the process list
+fn fetch(url: &str, session: Option<&Path>) -> Result<String> {
+    let cookie = match session {
+        Some(session) => {
+            let token = read_to_string(session)
+                .map_err(|e| anyhow!("Unable to read from {} - {}", session.display(), e))?;
+            format!("Cookie: session={}\n", token.trim())
+        }
+        None => String::new(),
+    };
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--header", "@-", url])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| anyhow!("Unable to run curl - {}", e))?;
+    child
+        .stdin
+        .take()
+        .expect("stdin is piped")
+        .write_all(cookie.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "Unable to fetch {} - {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    String::from_utf8(output.stdout).map_err(|e| anyhow!("Unable to read {} - {}", url, e))
+}
+
+fn write(json: &str, local: &BTreeMap<(String, usize), u64>, out: &mut impl Write) -> Result<()> {
+    let leaderboard: Leaderboard =
+        serde_json::from_str(json).map_err(|e| anyhow!("Invalid leaderboard - {}", e))?;
+    let year: u16 = leaderboard.event.parse().map_err(|_| {
+        anyhow!(
+            "Invalid leaderboard - '{}' is not a year",
+            leaderboard.event
+        )
+    })?;
+    let local = local_stars(local, year);
+
+    let mut members: Vec<_> = leaderboard.members.values().collect();
+    members.sort_by_key(|member| {
+        (
+            u64::MAX - member.local_score,
+            u64::MAX - member.stars,
+            member.name(),
+        )
+    });
+    let width = members
+        .iter()
+        .map(|member| member.name().len())
+        .max()
+        .unwrap_or(0)
+        .max("Day 25".len());
+
+    writeln!(
+        out,
+        "{} private leaderboard, {} members",
+        year,
+        members.len()
+    )?;
+    writeln!(out)?;
+    writeln!(out, " #  {:<width$}  score  stars", "name", width = width)?;
+    for (rank, member) in members.iter().enumerate() {
+        writeln!(
+            out,
+            "{:>2}  {:<width$}  {:>5}  {:>5}",
+            rank + 1,
+            member.name(),
+            member.local_score,
+            member.stars,
+            width = width
+        )?;
+    }
+
+    let mut days: Vec<u8> = members
+        .iter()
+        .flat_map(|member| member.completion_day_level.keys().copied())
+        .chain(local.keys().map(|(day, _)| *day))
+        .collect();
+    days.sort_unstable();
+    days.dedup();
+    for day in days {
+        let unlocked = unlocked(year, day);
+        let elapsed =
+            |stars: [Option<u64>; 2]| stars.map(|star| star.map(|at| at.saturating_sub(unlocked)));
+        let mut rows: Vec<_> = members
+            .iter()
+            .filter_map(|member| {
+                let stars = member.completion_day_level.get(&day)?;
+                let times =
+                    elapsed([1, 2].map(|part| stars.get(&part).map(|star| star.get_star_ts)));
+                Some((member.name(), times))
+            })
+            .collect();
+        rows.sort_by_key(|(name, [one, two])| {
+            (two.is_none(), *two, one.is_none(), *one, name.clone())
+        });
+
+        writeln!(out)?;
+        writeln!(
+            out,
+            "{:<width$}  {:>11}  {:>11}  {:>11}",
+            format!("Day {}", day),
+            "part 1",
+            "part 2",
+            "delta",
+            width = width
+        )?;
+        for (name, times) in rows.iter() {
+            write_times(out, name, *times, width)?;
+            writeln!(out)?;
+        }
+        let times = elapsed([1, 2].map(|part| local.get(&(day, part)).copied()));
+        if times.iter().any(Option::is_some) {
+            write_times(out, "local", times, width)?;
+            // placed by the last part answered, against the members who solved it
+            let part = usize::from(times[1].is_some());
+            let time = times[part].expect("a part was answered");
+            let solved = rows.iter().filter_map(|(_, times)| times[part]);
+            let rank = 1 + solved.clone().filter(|&other| other < time).count();
+            writeln!(
+                out,
+                "  {} of {} on part {}",
+                ordinal(rank),
+                solved.count() + 1,
+                part + 1
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn write_times(
+    out: &mut impl Write,
+    name: &str,
+    times: [Option<u64>; 2],
+    width: usize,
+) -> Result<()> {
+    let cell = |time: Option<u64>| time.map_or_else(|| "-".to_string(), duration);
+    let delta = match times {
+        [Some(one), Some(two)] => format!("+{}", duration(two.saturating_sub(one))),
+        _ => "-".to_string(),
+    };
+    write!(
+        out,
+        "{:<width$}  {:>11}  {:>11}  {:>11}",
+        name,
+        cell(times[0]),
+        cell(times[1]),
+        delta,
+        width = width
+    )?;
+    Ok(())
+}
+
+// The earliest local answer of each part of each day of `year`, whichever solver gave it
+fn local_stars(first: &BTreeMap<(String, usize), u64>, year: u16) -> BTreeMap<(u8, usize), u64> {
+    let mut stars = BTreeMap::new();
+    for ((day, part), &at) in first {
+        let (day_year, day) = advent2021::split_year(day);
+        if day_year.unwrap_or(DEFAULT_YEAR) != year {
+            continue;
+        }
+        if let Ok(number) = day.rsplit("::").next().unwrap_or(day).parse::<u8>() {
+            stars
+                .entry((number, *part))
+                .and_modify(|first: &mut u64| *first = at.min(*first))
+                .or_insert(at);
+        }
+    }
+    stars
+}
+
+/// Seconds since the epoch at which a puzzle unlocks, midnight EST (05:00 UTC) on its day of December
+fn unlocked(year: u16, day: u8) -> u64 {
+    let leap = |year: u64| {
+        year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+    };
+    let years: u64 = (1970..u64::from(year))
+        .map(|year| if leap(year) { 366 } else { 365 })
+        .sum();
+    // January to November
+    let months = 334 + u64::from(leap(u64::from(year)));
+    (years + months + u64::from(day) - 1) * 86400 + 5 * 3600
+}
+
+// Hours, minutes and seconds, or days, hours and minutes once a day has passed
+fn duration(seconds: u64) -> String {
+    let (days, hours) = (seconds / 86400, seconds / 3600 % 24);
+    let (minutes, seconds) = (seconds / 60 % 60, seconds % 60);
+    if days == 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}d {:02}:{:02}", days, hours, minutes)
+    }
+}
+
+fn ordinal(rank: usize) -> String {
+    let suffix = match (rank % 10, rank % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    };
+    format!("{}{}", rank, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JSON: &str = r#"{"event":"2021","owner_id":1,"members":{
+        "1":{"id":1,"name":"alice","stars":3,"local_score":12,"global_score":0,"last_star_ts":1638421500,
+            "completion_day_level":{
+                "1":{"1":{"get_star_ts":1638335100,"star_index":1},"2":{"get_star_ts":1638335400,"star_index":2}},
+                "2":{"1":{"get_star_ts":1638421500,"star_index":3}}}},
+        "2":{"id":2,"name":null,"stars":2,"local_score":4,"global_score":0,"last_star_ts":1638338400,
+            "completion_day_level":{
+                "1":{"1":{"get_star_ts":1638334920,"star_index":4},"2":{"get_star_ts":1638338400,"star_index":5}}}},
+        "3":{"id":3,"name":"carol","stars":0,"local_score":0,"global_score":0,"last_star_ts":0,
+            "completion_day_level":{}}}}"#;
+
+    #[test]
+    fn test_unlocked() {
+        assert_eq!(1638334800, unlocked(2021, 1));
+        assert_eq!(1606798800, unlocked(2020, 1));
+        assert_eq!(1608526800, unlocked(2020, 21));
+    }
+
+    #[test]
+    fn test_write() {
+        let local = [
+            (("1".to_string(), 1), 1638335000),
+            (("jhorstmann::1".to_string(), 1), 1638334990),
+            (("1".to_string(), 2), 1638336000),
+            (("2020:1".to_string(), 1), 1606798900),
+        ]
+        .into_iter()
+        .collect();
+        let mut out = Vec::new();
+        write(JSON, &local, &mut out).unwrap();
+        assert_eq!(
+            "\
+2021 private leaderboard, 3 members
+
+ #  name                 score  stars
+ 1  alice                   12      3
+ 2  (anonymous user #2)      4      2
+ 3  carol                    0      0
+
+Day 1                     part 1       part 2        delta
+alice                    0:05:00      0:10:00     +0:05:00
+(anonymous user #2)      0:02:00      1:00:00     +0:58:00
+local                    0:03:10      0:20:00     +0:16:50  2nd of 3 on part 2
+
+Day 2                     part 1       part 2        delta
+alice                    0:05:00            -            -
+",
+            String::from_utf8(out).unwrap()
+        );
+        assert!(write("{}", &local, &mut Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_formatting() {
+        assert_eq!("0:05:07", duration(307));
+        assert_eq!("23:59:59", duration(86399));
+        assert_eq!("1783d 00:57", duration(1783 * 86400 + 3420 + 8));
+        assert_eq!(
+            vec!["1st", "2nd", "3rd", "4th", "11th", "12th", "21st", "113th"],
+            [1, 2, 3, 4, 11, 12, 21, 113].map(ordinal).to_vec()
+        );
+    }
+}
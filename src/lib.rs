@@ -1,136 +1,269 @@
-use std::str::FromStr;
+#![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(not(any(feature = "std", feature = "alloc")))]
+compile_error!("either the `std` or `alloc` feature must be enabled");
+
+extern crate alloc;
+// tests read fixtures from disk regardless of the build
+#[cfg(all(test, not(feature = "std")))]
+#[macro_use]
+extern crate std;
+
+use alloc::{string::String, vec::Vec};
+use core::str::FromStr;
+
+pub mod checked;
+#[cfg(feature = "std")]
+pub mod config;
+#[cfg(feature = "day8")]
 pub mod day_eight;
+#[cfg(feature = "day11")]
 pub mod day_eleven;
+#[cfg(feature = "day15")]
 pub mod day_fifteen;
+#[cfg(feature = "day5")]
 pub mod day_five;
+#[cfg(feature = "day4")]
 pub mod day_four;
+#[cfg(feature = "day14")]
 pub mod day_fourteen;
+#[cfg(feature = "day9")]
 pub mod day_nine;
+#[cfg(feature = "day1")]
 pub mod day_one;
+#[cfg(feature = "day7")]
 pub mod day_seven;
+#[cfg(feature = "day6")]
 pub mod day_six;
+#[cfg(all(feature = "day6", feature = "jhorstmann"))]
 pub mod day_six_jhorstmann;
+#[cfg(feature = "day16")]
 pub mod day_sixteen;
+#[cfg(feature = "day10")]
 pub mod day_ten;
+#[cfg(feature = "day13")]
 pub mod day_thirteen;
+#[cfg(feature = "day3")]
 pub mod day_three;
+#[cfg(feature = "day12")]
 pub mod day_twelve;
+#[cfg(feature = "day2")]
 pub mod day_two;
+pub mod diagnostic;
+#[cfg(feature = "embed-inputs")]
+pub mod embedded;
+#[cfg(feature = "std")]
+pub mod external;
+pub mod lint;
+pub mod metadata;
+pub mod normalize;
+pub mod params;
+
+/// Hash collections from `std` or, in `no_std` builds, the equivalent `hashbrown` ones
+// unused in builds without the days using them
+#[allow(unused_imports)]
+pub(crate) mod collections {
+    #[cfg(not(feature = "std"))]
+    pub use hashbrown::{HashMap, HashSet};
+    #[cfg(feature = "std")]
+    pub use std::collections::{HashMap, HashSet};
+}
+
+/// Years with solvers, identifiers without a `YEAR:` prefix are looked up in `DEFAULT_YEAR`
+pub const YEARS: &[u16] = &[2021];
+pub const DEFAULT_YEAR: u16 = 2021;
+
+/// Canonical identifiers of every day `get_day` can solve in `DEFAULT_YEAR`
+pub const DAYS: &[&str] = &[
+    #[cfg(feature = "day1")]
+    "1",
+    #[cfg(feature = "day2")]
+    "2",
+    #[cfg(feature = "day3")]
+    "3",
+    #[cfg(feature = "day4")]
+    "4",
+    #[cfg(feature = "day5")]
+    "5",
+    #[cfg(feature = "day6")]
+    "6",
+    #[cfg(all(feature = "day6", feature = "jhorstmann"))]
+    "jhorstmann::6",
+    #[cfg(feature = "day7")]
+    "7",
+    #[cfg(feature = "day8")]
+    "8",
+    #[cfg(feature = "day9")]
+    "9",
+    #[cfg(feature = "day10")]
+    "10",
+    #[cfg(feature = "day11")]
+    "11",
+    #[cfg(feature = "day12")]
+    "12",
+    #[cfg(feature = "day13")]
+    "13",
+    #[cfg(feature = "day14")]
+    "14",
+    #[cfg(feature = "day15")]
+    "15",
+    #[cfg(feature = "day16")]
+    "16",
+];
 
 #[derive(Debug)]
 pub enum Error {
     InvalidDay,
+    InvalidYear,
 }
 
-pub fn get_day(
+/// Answers are `u64` whatever the width of `usize`, solvers whose arithmetic overflows fail with
+/// `checked::Overflow`
+pub type Solver = fn(&str) -> anyhow::Result<u64>;
+
+/// Splits the year off an identifier like `2021:5`, leaving namespaces (`jhorstmann::6`) intact
+pub fn split_year(id: &str) -> (Option<u16>, &str) {
+    match id.split_once(':') {
+        Some((year, day)) if !day.starts_with(':') => match year.parse() {
+            Ok(year) => (Some(year), day),
+            Err(_) => (None, id),
+        },
+        _ => (None, id),
+    }
+}
+
+/// Looks up a day of `DEFAULT_YEAR`, or of the year it is prefixed with (ie `2021:5`). The
+/// returned identifier is the canonical one within its year.
+pub fn get_day(id: &str) -> Result<(&'static str, &'static str, Solver, Solver), Error> {
+    let (year, day) = split_year(id);
+    get_year_day(year.unwrap_or(DEFAULT_YEAR), day)
+}
+
+pub fn get_year_day(
+    year: u16,
     day: &str,
-) -> Result<
-    (
-        &str,
-        &str,
-        fn(&str) -> anyhow::Result<usize>,
-        fn(&str) -> anyhow::Result<usize>,
-    ),
-    Error,
-> {
+) -> Result<(&'static str, &'static str, Solver, Solver), Error> {
+    match year {
+        2021 => get_2021_day(day),
+        _ => Err(Error::InvalidYear),
+    }
+}
+
+fn get_2021_day(day: &str) -> Result<(&'static str, &'static str, Solver, Solver), Error> {
     match day {
+        #[cfg(feature = "day1")]
         "1" | "one" => Ok((
             "1",
             day_one::INPUT_PATH,
             day_one::part_one,
             day_one::part_two,
         )),
+        #[cfg(feature = "day2")]
         "2" | "two" => Ok((
             "2",
             day_two::INPUT_PATH,
             day_two::part_one,
             day_two::part_two,
         )),
+        #[cfg(feature = "day3")]
         "3" | "three" => Ok((
             "3",
             day_three::INPUT_PATH,
             day_three::part_one,
             day_three::part_two,
         )),
+        #[cfg(feature = "day4")]
         "4" | "four" => Ok((
             "4",
             day_four::INPUT_PATH,
             day_four::part_one,
             day_four::part_two,
         )),
+        #[cfg(feature = "day5")]
         "5" | "five" => Ok((
             "5",
             day_five::INPUT_PATH,
             day_five::part_one,
             day_five::part_two,
         )),
+        #[cfg(feature = "day6")]
         "6" | "six" => Ok((
             "6",
             day_six::INPUT_PATH,
             day_six::part_one,
             day_six::part_two,
         )),
+        #[cfg(all(feature = "day6", feature = "jhorstmann"))]
         "jhorstmann::6" | "jhorstmann::six" => Ok((
             "jhorstmann::6",
             day_six_jhorstmann::INPUT_PATH,
             day_six_jhorstmann::part_one,
             day_six_jhorstmann::part_two,
         )),
+        #[cfg(feature = "day7")]
         "7" | "seven" => Ok((
             "7",
             day_seven::INPUT_PATH,
             day_seven::part_one,
             day_seven::part_two,
         )),
+        #[cfg(feature = "day8")]
         "8" | "eight" => Ok((
             "8",
             day_eight::INPUT_PATH,
             day_eight::part_one,
             day_eight::part_two,
         )),
+        #[cfg(feature = "day9")]
         "9" | "nine" => Ok((
             "9",
             day_nine::INPUT_PATH,
             day_nine::part_one,
             day_nine::part_two,
         )),
+        #[cfg(feature = "day10")]
         "10" | "ten" => Ok((
             "10",
             day_ten::INPUT_PATH,
             day_ten::part_one,
             day_ten::part_two,
         )),
+        #[cfg(feature = "day11")]
         "11" | "eleven" => Ok((
             "11",
             day_eleven::INPUT_PATH,
             day_eleven::part_one,
             day_eleven::part_two,
         )),
+        #[cfg(feature = "day12")]
         "12" | "twelve" => Ok((
             "12",
             day_twelve::INPUT_PATH,
             day_twelve::part_one,
             day_twelve::part_two,
         )),
+        #[cfg(feature = "day13")]
         "13" | "thirteen" => Ok((
             "13",
             day_thirteen::INPUT_PATH,
             day_thirteen::part_one,
             day_thirteen::part_two,
         )),
+        #[cfg(feature = "day14")]
         "14" | "fourteen" => Ok((
             "14",
             day_fourteen::INPUT_PATH,
             day_fourteen::part_one,
             day_fourteen::part_two,
         )),
+        #[cfg(feature = "day15")]
         "15" | "fifteen" => Ok((
             "15",
             day_fifteen::INPUT_PATH,
             day_fifteen::part_one,
             day_fifteen::part_two,
         )),
+        #[cfg(feature = "day16")]
         "16" | "sixteen" => Ok((
             "16",
             day_sixteen::INPUT_PATH,
@@ -141,12 +274,151 @@ pub fn get_day(
     }
 }
 
+/// A solver reading its puzzle constants (ie the number of simulated days) from `Params`
+pub type ParameterizedSolver = fn(&str, &params::Params) -> anyhow::Result<u64>;
+
+/// Looks up the parameters of a day returned by `get_day` and the part one and part two solvers
+/// that read them. `get_day`'s solvers are these with the default values.
+pub fn get_parameterized(
+    day: &str,
+) -> Option<(
+    &'static [params::Parameter],
+    ParameterizedSolver,
+    ParameterizedSolver,
+)> {
+    match split_year(day) {
+        #[cfg(feature = "day1")]
+        (None | Some(2021), "1") => Some((
+            day_one::PARAMETERS,
+            day_one::part_one_with,
+            day_one::part_two_with,
+        )),
+        #[cfg(feature = "day6")]
+        (None | Some(2021), "6") => Some((
+            day_six::PARAMETERS,
+            day_six::part_one_with,
+            day_six::part_two_with,
+        )),
+        #[cfg(feature = "day9")]
+        (None | Some(2021), "9") => Some((
+            day_nine::PARAMETERS,
+            day_nine::part_one_with,
+            day_nine::part_two_with,
+        )),
+        #[cfg(feature = "day11")]
+        (None | Some(2021), "11") => Some((
+            day_eleven::PARAMETERS,
+            day_eleven::part_one_with,
+            day_eleven::part_two_with,
+        )),
+        #[cfg(feature = "day14")]
+        (None | Some(2021), "14") => Some((
+            day_fourteen::PARAMETERS,
+            day_fourteen::part_one_with,
+            day_fourteen::part_two_with,
+        )),
+        #[cfg(feature = "day15")]
+        (None | Some(2021), "15") => Some((
+            day_fifteen::PARAMETERS,
+            day_fifteen::part_one_with,
+            day_fifteen::part_two_with,
+        )),
+        _ => None,
+    }
+}
+
+/// Looks up the metadata of a day returned by `get_day`
+pub fn get_metadata(day: &str) -> Option<&'static metadata::Metadata> {
+    match split_year(day) {
+        #[cfg(feature = "day1")]
+        (None | Some(2021), "1") => Some(&day_one::METADATA),
+        #[cfg(feature = "day2")]
+        (None | Some(2021), "2") => Some(&day_two::METADATA),
+        #[cfg(feature = "day3")]
+        (None | Some(2021), "3") => Some(&day_three::METADATA),
+        #[cfg(feature = "day4")]
+        (None | Some(2021), "4") => Some(&day_four::METADATA),
+        #[cfg(feature = "day5")]
+        (None | Some(2021), "5") => Some(&day_five::METADATA),
+        #[cfg(feature = "day6")]
+        (None | Some(2021), "6") => Some(&day_six::METADATA),
+        #[cfg(all(feature = "day6", feature = "jhorstmann"))]
+        (None | Some(2021), "jhorstmann::6") => Some(&day_six_jhorstmann::METADATA),
+        #[cfg(feature = "day7")]
+        (None | Some(2021), "7") => Some(&day_seven::METADATA),
+        #[cfg(feature = "day8")]
+        (None | Some(2021), "8") => Some(&day_eight::METADATA),
+        #[cfg(feature = "day9")]
+        (None | Some(2021), "9") => Some(&day_nine::METADATA),
+        #[cfg(feature = "day10")]
+        (None | Some(2021), "10") => Some(&day_ten::METADATA),
+        #[cfg(feature = "day11")]
+        (None | Some(2021), "11") => Some(&day_eleven::METADATA),
+        #[cfg(feature = "day12")]
+        (None | Some(2021), "12") => Some(&day_twelve::METADATA),
+        #[cfg(feature = "day13")]
+        (None | Some(2021), "13") => Some(&day_thirteen::METADATA),
+        #[cfg(feature = "day14")]
+        (None | Some(2021), "14") => Some(&day_fourteen::METADATA),
+        #[cfg(feature = "day15")]
+        (None | Some(2021), "15") => Some(&day_fifteen::METADATA),
+        #[cfg(feature = "day16")]
+        (None | Some(2021), "16") => Some(&day_sixteen::METADATA),
+        _ => None,
+    }
+}
+
+/// Looks up a function rendering the parsed input of a day returned by `get_day`
+pub fn get_inspector(day: &str) -> Option<fn(&str) -> anyhow::Result<String>> {
+    match split_year(day) {
+        #[cfg(feature = "day4")]
+        (None | Some(2021), "4") => Some(day_four::inspect),
+        #[cfg(feature = "day12")]
+        (None | Some(2021), "12") => Some(day_twelve::inspect),
+        #[cfg(feature = "day16")]
+        (None | Some(2021), "16") => Some(day_sixteen::inspect),
+        _ => None,
+    }
+}
+
+/// Looks up the format checks of a day returned by `get_day`
+pub fn get_linter(day: &str) -> Option<lint::Linter> {
+    match split_year(day) {
+        #[cfg(feature = "day4")]
+        (None | Some(2021), "4") => Some(day_four::lint),
+        #[cfg(feature = "day5")]
+        (None | Some(2021), "5") => Some(day_five::lint),
+        #[cfg(feature = "day6")]
+        (None | Some(2021), "6") => Some(day_six::lint),
+        #[cfg(all(feature = "day6", feature = "jhorstmann"))]
+        (None | Some(2021), "jhorstmann::6") => Some(day_six::lint),
+        #[cfg(feature = "day11")]
+        (None | Some(2021), "11") => Some(day_eleven::lint),
+        #[cfg(feature = "day16")]
+        (None | Some(2021), "16") => Some(day_sixteen::lint),
+        _ => None,
+    }
+}
+
+/// A solver answering both parts from one pass, for days whose parts share their work
+pub type BothSolver = fn(&str) -> anyhow::Result<(u64, u64)>;
+
+/// Looks up a solver of both parts of a day returned by `get_day`, answering as its part one and
+/// part two solvers with the default parameters
+pub fn get_both(day: &str) -> Option<BothSolver> {
+    match split_year(day) {
+        #[cfg(feature = "day9")]
+        (None | Some(2021), "9") => Some(day_nine::solve_both),
+        #[cfg(feature = "day11")]
+        (None | Some(2021), "11") => Some(day_eleven::solve_both),
+        #[cfg(feature = "day16")]
+        (None | Some(2021), "16") => Some(day_sixteen::solve_both),
+        _ => None,
+    }
+}
+
 pub fn load_integers(contents: &str) -> anyhow::Result<Vec<usize>> {
-    let integers: anyhow::Result<Vec<_>> = contents
-        .lines()
-        .map(|s| s.parse().map_err(anyhow::Error::new))
-        .collect();
-    integers
+    stream_integers(contents.lines().map(Ok)).collect()
 }
 
 pub fn read_to_lines(contents: &str) -> anyhow::Result<Vec<String>> {
@@ -157,11 +429,145 @@ pub fn read_to_lines(contents: &str) -> anyhow::Result<Vec<String>> {
     Ok(lines)
 }
 
+/// A solver consuming its input a line at a time, so it can run in bounded memory
+pub type StreamingSolver =
+    fn(&mut dyn Iterator<Item = anyhow::Result<String>>) -> anyhow::Result<u64>;
+
+/// Looks up the streaming solvers of a day returned by `get_day`
+pub fn get_streaming(day: &str) -> Option<(StreamingSolver, StreamingSolver)> {
+    match split_year(day) {
+        #[cfg(feature = "day1")]
+        (None | Some(2021), "1") => Some((
+            |lines| day_one::part_one_lines(lines),
+            |lines| day_one::part_two_lines(lines),
+        )),
+        #[cfg(feature = "day2")]
+        (None | Some(2021), "2") => Some((
+            |lines| day_two::part_one_lines(lines),
+            |lines| day_two::part_two_lines(lines),
+        )),
+        #[cfg(feature = "day3")]
+        (None | Some(2021), "3") => Some((
+            |lines| day_three::part_one_lines(lines),
+            |lines| day_three::part_two_lines(lines),
+        )),
+        #[cfg(feature = "day10")]
+        (None | Some(2021), "10") => Some((
+            |lines| day_ten::part_one_lines(lines),
+            |lines| day_ten::part_two_lines(lines),
+        )),
+        _ => None,
+    }
+}
+
+/// Reads an input or fixture from disk or, in builds with the `embed-inputs` feature, from the copy
+/// embedded in the crate when it isn't on disk
+#[cfg(feature = "std")]
+pub fn read_input(path: impl AsRef<std::path::Path>) -> std::io::Result<String> {
+    let path = path.as_ref();
+    match std::fs::read_to_string(path) {
+        #[cfg(feature = "embed-inputs")]
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            embedded::find(path).map(str::to_string).ok_or(e)
+        }
+        result => result,
+    }
+}
+
+/// Reads lines one at a time (ie from a file or stdin), the streaming counterpart of `read_to_lines`
+#[cfg(feature = "std")]
+pub fn stream_lines(reader: impl std::io::BufRead) -> impl Iterator<Item = anyhow::Result<String>> {
+    reader.lines().map(|line| line.map_err(anyhow::Error::new))
+}
+
+/// Parses lines as they are read, the streaming counterpart of `load_integers`
+pub fn stream_integers<S: AsRef<str>>(
+    lines: impl Iterator<Item = anyhow::Result<S>>,
+) -> impl Iterator<Item = anyhow::Result<usize>> {
+    lines.map(|line| Ok(line?.as_ref().parse()?))
+}
+
+/// Runs `f` over the values of `items` until the first error, which is returned instead of the
+/// result of `f`. Lets solvers written against plain iterators consume fallible streams.
+pub fn process_results<I, T, R>(
+    items: I,
+    f: impl FnOnce(UntilError<'_, I>) -> R,
+) -> anyhow::Result<R>
+where
+    I: Iterator<Item = anyhow::Result<T>>,
+{
+    let mut error = None;
+    let result = f(UntilError {
+        items,
+        error: &mut error,
+    });
+    error.map_or(Ok(result), Err)
+}
+
+/// Iterator of `process_results`
+pub struct UntilError<'a, I> {
+    items: I,
+    error: &'a mut Option<anyhow::Error>,
+}
+
+impl<I, T> Iterator for UntilError<'_, I>
+where
+    I: Iterator<Item = anyhow::Result<T>>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        match self.items.next()? {
+            Ok(item) => Some(item),
+            Err(e) => {
+                *self.error = Some(e);
+                None
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::fs::read_to_string;
 
-    use super::load_integers;
+    use super::{
+        get_day, get_metadata, load_integers, process_results, split_year, stream_integers, DAYS,
+    };
+
+    #[test]
+    fn test_days_are_canonical() {
+        DAYS.iter().for_each(|day| {
+            let (canonical, _, _, _) = get_day(day).expect("listed day is not solvable");
+            assert_eq!(*day, canonical);
+            assert!(get_metadata(day).is_some(), "day {} has no metadata", day);
+        });
+    }
+
+    #[test]
+    fn test_split_year() {
+        assert_eq!((Some(2021), "5"), split_year("2021:5"));
+        assert_eq!(
+            (Some(2021), "jhorstmann::6"),
+            split_year("2021:jhorstmann::6")
+        );
+        assert_eq!((None, "jhorstmann::6"), split_year("jhorstmann::6"));
+        assert_eq!((None, "five"), split_year("five"));
+        #[cfg(feature = "day5")]
+        assert!(get_day("2021:five").is_ok());
+        assert!(get_day("2020:5").is_err());
+    }
+
+    #[test]
+    fn test_process_results() {
+        let sum = |lines: &[&str]| {
+            process_results(stream_integers(lines.iter().map(Ok)), |nums| {
+                nums.sum::<usize>()
+            })
+        };
+        assert_eq!(6, sum(&["1", "2", "3"]).unwrap());
+        assert!(sum(&["1", "two", "3"]).is_err());
+    }
 
     #[test]
     fn test_load_integers() {
@@ -0,0 +1,90 @@
+use alloc::string::String;
+use core::fmt;
+
+/// Checks an input against the format of its day without solving it, reporting every problem
+pub type Linter = fn(&str) -> alloc::vec::Vec<Problem>;
+
+/// A way an input deviates from the format of its day
+#[derive(Clone, Debug, PartialEq)]
+pub struct Problem {
+    /// Counted from 1
+    pub line: usize,
+    /// Counted in characters from 1, when the problem lies within the line
+    pub column: Option<usize>,
+    pub message: String,
+}
+
+impl Problem {
+    pub fn new(line: usize, message: String) -> Problem {
+        Problem {
+            line,
+            column: None,
+            message,
+        }
+    }
+
+    pub fn at(self, column: usize) -> Problem {
+        Problem {
+            column: Some(column),
+            ..self
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "line {}, column {}: {}", self.line, column, self.message),
+            None => write!(f, "line {}: {}", self.line, self.message),
+        }
+    }
+}
+
+/// The fields of a line split by `separator`, with the column each starts at
+#[cfg(any(feature = "day4", feature = "day6"))]
+pub(crate) fn fields(line: &str, separator: char) -> impl Iterator<Item = (usize, &str)> {
+    let mut column = 1;
+    line.split(separator).map(move |field| {
+        let start = column;
+        column += field.chars().count() + 1;
+        (start, field)
+    })
+}
+
+/// The space separated words of a line, with the column each starts at
+#[cfg(feature = "day4")]
+pub(crate) fn words(line: &str) -> impl Iterator<Item = (usize, &str)> {
+    fields(line, ' ').filter(|(_, word)| !word.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[cfg(feature = "day4")]
+    #[test]
+    fn test_fields() {
+        use alloc::{vec, vec::Vec};
+
+        assert_eq!(
+            vec![(1, "3"), (3, "41"), (6, "")],
+            fields("3,41,", ',').collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![(2, "8"), (5, "23")],
+            words(" 8  23").collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_display() {
+        let problem = Problem::new(3, "'x' is not a number".to_string());
+        assert_eq!("line 3: 'x' is not a number", problem.to_string());
+        assert_eq!(
+            "line 3, column 7: 'x' is not a number",
+            problem.at(7).to_string()
+        );
+    }
+}
@@ -0,0 +1,105 @@
+use std::io::{self, Write};
+
+use advent2021::config::Config;
+use advent2021::metadata::Tag;
+
+use crate::inputs::answers_path;
+
+const HEADERS: [&str; 7] = [
+    "DAY",
+    "TITLE",
+    "AUTHOR",
+    "TAGS",
+    "PARAMETERS",
+    "EXAMPLES",
+    "ANSWERS",
+];
+
+/// Writes a table of the solvers of `DEFAULT_YEAR`, only those tagged with `tag` when given. The
+/// answers column tells whether the default input has an answers file.
+pub fn write(out: &mut impl Write, config: &Config, tag: Option<Tag>) -> io::Result<()> {
+    let rows: Vec<[String; 7]> = advent2021::DAYS
+        .iter()
+        .filter_map(|day| {
+            let metadata = advent2021::get_metadata(day)?;
+            if tag.is_some_and(|tag| !metadata.tags.contains(&tag)) {
+                return None;
+            }
+            let (_, input_path, _, _) = advent2021::get_day(day).ok()?;
+            let parameters: Vec<_> = advent2021::get_parameterized(day)
+                .map_or(&[][..], |(parameters, _, _)| parameters)
+                .iter()
+                .map(|parameter| parameter.name)
+                .collect();
+            let answers = answers_path(&config.input_path(input_path)).is_file();
+            Some([
+                day.to_string(),
+                metadata.title.to_string(),
+                metadata.author.to_string(),
+                join(metadata.tags.iter().map(Tag::name)),
+                join(parameters.into_iter()),
+                yes_no(metadata.examples),
+                yes_no(answers),
+            ])
+        })
+        .collect();
+
+    let widths = HEADERS.map(|header| header.len());
+    let widths = rows.iter().fold(widths, |mut widths, row| {
+        widths
+            .iter_mut()
+            .zip(row)
+            .for_each(|(width, cell)| *width = (*width).max(cell.chars().count()));
+        widths
+    });
+    let header = HEADERS.map(str::to_string);
+    std::iter::once(&header)
+        .chain(rows.iter())
+        .try_for_each(|row| {
+            let line: Vec<_> = row
+                .iter()
+                .zip(widths)
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect();
+            writeln!(out, "{}", line.join("  ").trim_end())
+        })
+}
+
+fn join<'a>(values: impl Iterator<Item = &'a str>) -> String {
+    let values: Vec<_> = values.collect();
+    if values.is_empty() {
+        "-".to_string()
+    } else {
+        values.join(",")
+    }
+}
+
+fn yes_no(value: bool) -> String {
+    if value { "yes" } else { "no" }.to_string()
+}
+
+// the listing is of every solver
+#[cfg(all(test, feature = "all-days"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write() {
+        let mut out = Vec::new();
+        write(&mut out, &Config::default(), Some(Tag::Graph)).unwrap();
+        assert_eq!(
+            "\
+DAY  TITLE            AUTHOR    TAGS        PARAMETERS  EXAMPLES  ANSWERS
+12   Passage Pathing  medwards  graph       -           yes       no
+15   Chiton           medwards  grid,graph  tiles       yes       no
+",
+            String::from_utf8(out).unwrap()
+        );
+
+        let mut out = Vec::new();
+        write(&mut out, &Config::default(), None).unwrap();
+        let table = String::from_utf8(out).unwrap();
+        assert_eq!(advent2021::DAYS.len() + 1, table.lines().count());
+        assert!(table.contains("jhorstmann::6  Lanternfish"));
+    }
+}
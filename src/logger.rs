@@ -0,0 +1,110 @@
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Result};
+use log::{LevelFilter, Log, Metadata, Record};
+
+/// Writes the events of solvers to stderr. Solvers log under their day (ie `day12`) and the level
+/// can be set per day with `--log day12=trace`, or for every day with `-v`, `-vv` and `-vvv`.
+pub struct Logger {
+    default: LevelFilter,
+    targets: Vec<(String, LevelFilter)>,
+}
+
+impl Logger {
+    /// `directives` are comma separated `target=level` pairs or a bare level for every target
+    pub fn new(verbosity: u64, directives: &[&str]) -> Result<Logger> {
+        let mut logger = Logger {
+            default: match verbosity {
+                0 => LevelFilter::Warn,
+                1 => LevelFilter::Info,
+                2 => LevelFilter::Debug,
+                _ => LevelFilter::Trace,
+            },
+            targets: Vec::new(),
+        };
+        directives
+            .iter()
+            .flat_map(|directive| directive.split(','))
+            .try_for_each(|directive| -> Result<()> {
+                let parse = |level| {
+                    LevelFilter::from_str(level)
+                        .map_err(|_| anyhow!("'{}' is not a log level", level))
+                };
+                match directive.split_once('=') {
+                    Some((target, level)) => {
+                        logger.targets.push((target.to_string(), parse(level)?));
+                    }
+                    None => logger.default = parse(directive)?,
+                }
+                Ok(())
+            })?;
+        Ok(logger)
+    }
+
+    /// Installs the logger, events above every configured level are skipped before formatting
+    pub fn install(self) {
+        let max_level = self
+            .targets
+            .iter()
+            .map(|(_, level)| *level)
+            .fold(self.default, Ord::max);
+        log::set_max_level(max_level);
+        log::set_logger(Box::leak(Box::new(self))).expect("a logger is already installed");
+    }
+
+    // The most specific target wins, `day1` configures `day1` and `day1::*` but not `day12`
+    fn level(&self, target: &str) -> LevelFilter {
+        self.targets
+            .iter()
+            .filter(|(prefix, _)| {
+                target
+                    .strip_prefix(prefix.as_str())
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with("::"))
+            })
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(self.default, |(_, level)| *level)
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level(metadata.target())
+    }
+
+    fn log(&self, record: &Record) {
+        if self.enabled(record.metadata()) {
+            // a closed stderr leaves nowhere to report the failure
+            let _ = writeln!(
+                io::stderr().lock(),
+                "[{} {}] {}",
+                record.level(),
+                record.target(),
+                record.args()
+            );
+        }
+    }
+
+    fn flush(&self) {
+        let _ = io::stderr().flush();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level() {
+        let logger = Logger::new(1, &["day12=trace,day1=debug", "day4=off"]).unwrap();
+        assert_eq!(LevelFilter::Trace, logger.level("day12"));
+        assert_eq!(LevelFilter::Debug, logger.level("day1"));
+        assert_eq!(LevelFilter::Off, logger.level("day4"));
+        assert_eq!(LevelFilter::Info, logger.level("day13"));
+        assert_eq!(
+            LevelFilter::Error,
+            Logger::new(3, &["error"]).unwrap().default
+        );
+        assert!(Logger::new(0, &["day12=loud"]).is_err());
+    }
+}
@@ -1,48 +1,652 @@
-use std::fs::read_to_string;
+use std::fs::File;
+use std::io::{self, BufReader, IsTerminal, Write};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use advent2021::config::{self, Config, Format, PartSolver};
+use advent2021::diagnostic::Diagnostic;
+use advent2021::metadata::{Tag, TAGS};
+use advent2021::normalize;
+use advent2021::params::Params;
+use advent2021::BothSolver;
+use inputs::Selection;
+use output::Outcome;
+
+mod cache;
+mod completions;
+mod dashboard;
+mod examples;
+mod history;
+mod inputs;
+mod leaderboard;
+mod list;
+mod logger;
+mod man;
+mod output;
+mod repl;
+mod report;
+mod serve;
+
+#[global_allocator]
+static ALLOCATOR: report::CountingAllocator = report::CountingAllocator;
+
+const AUTHOR: &str = "Michael Edwards <medwards@walledcity.ca>";
+
+fn app() -> clap::App<'static> {
+    app_with_days(&[])
+}
+
+// DAY also takes days no solver lists, ie `2021:6` or external ones, which parsing against the days
+// as its possible values would refuse, so only the completion scripts are given them
+fn app_with_days(days: &'static [&'static str]) -> clap::App<'static> {
+    clap::App::new("Advent of Code 2021 Solver")
+        .author(AUTHOR)
+        .arg(
+            clap::Arg::new("config")
+                .long("config")
+                .about("Config file to use instead of ./advent.toml")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("year")
+                .long("year")
+                .about("Year of the days given without one, overrides the config file")
+                .takes_value(true),
+        )
+        .arg(
+            clap::Arg::new("strict-input")
+                .long("strict-input")
+                .about("Report inputs needing normalisation instead of fixing them"),
+        )
+        .arg(
+            clap::Arg::new("verbose")
+                .short('v')
+                .about("Log solver events, repeat for more detail")
+                .multiple_occurrences(true),
+        )
+        .arg(
+            clap::Arg::new("log")
+                .long("log")
+                .about("Log level per day, ie day12=trace")
+                .takes_value(true)
+                .multiple_occurrences(true),
+        )
+        .subcommand(
+            clap::App::new("day")
+                .about("Select day to solve")
+                .arg(
+                    clap::Arg::new("DAY")
+                        .possible_values(days)
+                        .multiple_occurrences(true)
+                        .min_values(1)
+                        .required(true),
+                )
+                .arg(
+                    clap::Arg::new("format")
+                        .long("format")
+                        .about("Output format, overrides the config file")
+                        .takes_value(true)
+                        .possible_values(["text", "json"]),
+                )
+                .arg(
+                    clap::Arg::new("input-name")
+                        .long("input-name")
+                        .about("Solve the named input in the day's input directory")
+                        .takes_value(true),
+                )
+                .arg(
+                    clap::Arg::new("all-inputs")
+                        .long("all-inputs")
+                        .about("Solve every input in the day's input directory")
+                        .conflicts_with("input-name"),
+                )
+                .arg(
+                    clap::Arg::new("no-cache")
+                        .long("no-cache")
+                        .about("Solve even when the answer is cached, without caching it"),
+                )
+                .arg(
+                    clap::Arg::new("param")
+                        .long("param")
+                        .about("Override a puzzle constant of every DAY, ie days=18 for day 6")
+                        .takes_value(true)
+                        .multiple_occurrences(true),
+                ),
+        )
+        .subcommand(
+            clap::App::new("list")
+                .about("List the solvers with their metadata")
+                .arg(
+                    clap::Arg::new("tag")
+                        .long("tag")
+                        .about("Only list solvers with this tag")
+                        .takes_value(true)
+                        .possible_values(TAGS.iter().map(Tag::name)),
+                ),
+        )
+        .subcommand(
+            clap::App::new("repl").about("Interactively load inputs, tweak parameters and solve"),
+        )
+        .subcommand(
+            clap::App::new("serve")
+                .about("Serve the solvers over HTTP on localhost")
+                .arg(
+                    clap::Arg::new("port")
+                        .long("port")
+                        .about("Port to listen on")
+                        .takes_value(true)
+                        .default_value("2021"),
+                ),
+        )
+        .subcommand(
+            clap::App::new("completions")
+                .about("Print a shell completion script")
+                .arg(
+                    clap::Arg::new("SHELL")
+                        .possible_values(completions::SHELLS)
+                        .required(true),
+                ),
+        )
+        .subcommand(clap::App::new("man").about("Print a man page"))
+        .subcommand(clap::App::new("config").about("Print the configuration in effect"))
+        .subcommand(
+            clap::App::new("stream")
+                .about("Solve a part of a day a line at a time, for inputs too large to load")
+                .arg(clap::Arg::new("DAY").possible_values(days).required(true))
+                .arg(
+                    clap::Arg::new("PART")
+                        .possible_values(["1", "2"])
+                        .required(true),
+                )
+                .arg(clap::Arg::new("FILE").about("Input to read, stdin if absent or -"))
+                .arg(
+                    clap::Arg::new("format")
+                        .long("format")
+                        .about("Output format, overrides the config file")
+                        .takes_value(true)
+                        .possible_values(["text", "json"]),
+                ),
+        )
+        .subcommand(
+            clap::App::new("cache")
+                .about("Manage the cache of answers")
+                .setting(clap::AppSettings::SubcommandRequiredElseHelp)
+                .subcommand(clap::App::new("clear").about("Remove every cached answer")),
+        )
+        .subcommand(
+            clap::App::new("report")
+                .about("Solve every day and print a Markdown table of answers and timings")
+                .arg(
+                    clap::Arg::new("runs")
+                        .long("runs")
+                        .about("Number of solves to take the median time of")
+                        .takes_value(true)
+                        .default_value("5"),
+                )
+                .arg(
+                    clap::Arg::new("html")
+                        .long("html")
+                        .about("Print an HTML page instead"),
+                ),
+        )
+        .subcommand(
+            clap::App::new("lint")
+                .about("Check an input against the format of a day without solving it")
+                .arg(clap::Arg::new("DAY").possible_values(days).required(true))
+                .arg(clap::Arg::new("FILE").about("Input to check, the day's input if absent")),
+        )
+        .subcommand(
+            clap::App::new("examples")
+                .about("Save the example input and answers of a puzzle page as a fixture")
+                .arg(clap::Arg::new("DAY").possible_values(days).required(true))
+                .arg(
+                    clap::Arg::new("PAGE")
+                        .about("Puzzle page saved as HTML")
+                        .required(true),
+                )
+                .arg(
+                    clap::Arg::new("force")
+                        .long("force")
+                        .about("Replace the fixture if it exists"),
+                ),
+        )
+        .subcommand(
+            clap::App::new("dashboard")
+                .about("Solve days on a table of their progress, every day if none are given")
+                .arg(
+                    clap::Arg::new("DAY")
+                        .possible_values(days)
+                        .multiple_occurrences(true),
+                ),
+        )
+        .subcommand(
+            clap::App::new("leaderboard")
+                .about("Print a private leaderboard next to the local solve times")
+                .arg(
+                    clap::Arg::new("FILE").about(
+                        "Exported leaderboard JSON, fetched from the configured URL if absent",
+                    ),
+                ),
+        )
+}
+
+// clap adds --help and --version to every subcommand, which the man page leaves out
+fn arguments<'a, 'help>(app: &'a clap::App<'help>) -> impl Iterator<Item = &'a clap::Arg<'help>> {
+    app.get_arguments()
+        .filter(|arg| !matches!(arg.get_name(), "help" | "version"))
+}
 
 fn main() {
-    let mut app = clap::App::new("Advent of Code 2021 Solver")
-        .author("Michael Edwards <medwards@walledcity.ca>")
-        .subcommand(
-            clap::App::new("day").about("Select day to solve").arg(
-                clap::Arg::new("DAY")
-                    .multiple_occurrences(true)
-                    .min_values(1)
-                    .required(true),
-            ),
-        );
-    let subcommand_error = app.error(
+    let mut cli = app();
+    let subcommand_error = cli.error(
         clap::ErrorKind::MissingSubcommand,
         "Missing subcommand which wasn't expected. Did you mean 'day'?",
     );
-    let invalid_day_error = app.error(
+    let invalid_day_error = cli.error(
         clap::ErrorKind::InvalidValue,
         "a DAY argument wasn't recognized",
     );
-    let matches = app.get_matches();
+    let unstreamable_day_error = cli.error(
+        clap::ErrorKind::InvalidValue,
+        "DAY has no streaming solvers, only days 1, 2, 3 and 10 do",
+    );
+    let unlintable_day_error = cli.error(
+        clap::ErrorKind::InvalidValue,
+        "DAY has no format checks, only days 4, 5, 6, 11 and 16 do",
+    );
+    let invalid_param_error = cli.error(
+        clap::ErrorKind::InvalidValue,
+        "PARAM must be name=value, naming a parameter of every DAY",
+    );
+    let missing_leaderboard_error = cli.error(
+        clap::ErrorKind::MissingRequiredArgument,
+        "FILE is required unless a leaderboard URL is configured",
+    );
+    let invalid_port_error = cli.error(clap::ErrorKind::InvalidValue, "PORT must be a number");
+    let invalid_runs_error = cli.error(clap::ErrorKind::InvalidValue, "RUNS must be a number");
+    let invalid_year_error = cli.error(clap::ErrorKind::InvalidValue, "YEAR must be a number");
+    let invalid_log_error = cli.error(
+        clap::ErrorKind::InvalidValue,
+        "LOG must be a level or target=level, ie day12=trace",
+    );
+    let matches = cli.get_matches();
+    let directives: Vec<_> = matches.values_of("log").into_iter().flatten().collect();
+    logger::Logger::new(matches.occurrences_of("verbose"), directives.as_slice())
+        .unwrap_or_else(|_| invalid_log_error.exit())
+        .install();
+    let mut config =
+        Config::discover(matches.value_of("config")).unwrap_or_else(|e| panic!("{}", e));
+    if matches.is_present("strict-input") {
+        config.normalize = config::Normalize::Strict;
+    }
+    if let Some(year) = matches.value_of("year") {
+        config.year = Some(year.parse().unwrap_or_else(|_| invalid_year_error.exit()));
+    }
 
-    let days = if let Some(subcommand) = matches.subcommand_matches("day") {
-        subcommand.values_of("DAY").expect("day was not provided")
-    } else {
-        subcommand_error.exit();
+    let (days, format, selection, overrides, cache) = match matches.subcommand() {
+        Some(("day", subcommand)) => (
+            subcommand.values_of("DAY").expect("day was not provided"),
+            subcommand
+                .value_of("format")
+                .map_or(config.format, |format| {
+                    format.parse().expect("clap only accepts known formats")
+                }),
+            match subcommand.value_of("input-name") {
+                Some(name) => Selection::Named(name),
+                None if subcommand.is_present("all-inputs") => Selection::All,
+                None => Selection::Default,
+            },
+            subcommand
+                .values_of("param")
+                .into_iter()
+                .flatten()
+                .map(advent2021::params::parse_override)
+                .collect::<anyhow::Result<Vec<_>>>()
+                .unwrap_or_else(|_| invalid_param_error.exit()),
+            if subcommand.is_present("no-cache") {
+                None
+            } else {
+                // running without a cache beats not running
+                cache::Cache::open(config.cache())
+                    .map_err(
+                        |e| log::warn!(target: "cache", "Unable to identify the build - {}", e),
+                    )
+                    .ok()
+            },
+        ),
+        Some(("list", subcommand)) => {
+            let tag = subcommand
+                .value_of("tag")
+                .map(|tag| tag.parse().expect("clap only accepts known tags"));
+            return list::write(&mut io::stdout(), &config, tag)
+                .expect("Unable to write to stdout");
+        }
+        Some(("repl", _)) => return repl::run(&config).expect("Unable to read from stdin"),
+        Some(("serve", subcommand)) => {
+            let port = subcommand
+                .value_of("port")
+                .and_then(|port| port.parse().ok())
+                .unwrap_or_else(|| invalid_port_error.exit());
+            return serve::run(port, config.strict_input())
+                .unwrap_or_else(|e| panic!("Unable to serve - {}", e));
+        }
+        Some(("completions", subcommand)) => {
+            let shell = subcommand
+                .value_of("SHELL")
+                .expect("shell was not provided");
+            return completions::generate(
+                shell,
+                &mut app_with_days(advent2021::DAYS),
+                &mut std::io::stdout(),
+            );
+        }
+        Some(("man", _)) => {
+            return man::generate(&app(), &mut std::io::stdout())
+                .expect("Unable to write to stdout")
+        }
+        Some(("config", _)) => return print!("{}", config),
+        Some(("cache", subcommand)) => match subcommand.subcommand() {
+            Some(("clear", _)) => {
+                let directory = config.cache();
+                let cleared = cache::clear(&directory)
+                    .unwrap_or_else(|e| panic!("Unable to clear {} - {}", directory.display(), e));
+                return println!("Removed {} cached answers", cleared);
+            }
+            _ => unreachable!("clap requires a cache subcommand"),
+        },
+        Some(("report", subcommand)) => {
+            let runs = subcommand
+                .value_of("runs")
+                .and_then(|runs| runs.parse().ok())
+                .unwrap_or_else(|| invalid_runs_error.exit());
+            return report::run(
+                &config,
+                runs,
+                subcommand.is_present("html"),
+                &mut io::stdout(),
+            )
+            .expect("Unable to write to stdout");
+        }
+        Some(("lint", subcommand)) => {
+            let day = subcommand.value_of("DAY").expect("day was not provided");
+            let (day, input_path, _, _) = config
+                .get_day(day)
+                .unwrap_or_else(|_| invalid_day_error.exit());
+            let linter =
+                advent2021::get_linter(&day).unwrap_or_else(|| unlintable_day_error.exit());
+            let path = subcommand
+                .value_of("FILE")
+                .map_or(input_path, PathBuf::from);
+            let contents = advent2021::read_input(&path)
+                .unwrap_or_else(|e| panic!("Unable to read from {} - {}", path.display(), e));
+            // in strict mode an input needing normalisation isn't checked any further
+            let problems: Vec<String> = match normalize::normalize(&contents, config.strict_input())
+            {
+                Ok(contents) => linter(&contents).iter().map(|p| p.to_string()).collect(),
+                Err(e) => vec![e.to_string()],
+            };
+            if problems.is_empty() {
+                return println!("{}: no problems", path.display());
+            }
+            problems
+                .iter()
+                .for_each(|problem| println!("{}: {}", path.display(), problem));
+            std::process::exit(1);
+        }
+        Some(("examples", subcommand)) => {
+            let day = subcommand.value_of("DAY").expect("day was not provided");
+            let (day, _, _, _) = config
+                .get_day(day)
+                .unwrap_or_else(|_| invalid_day_error.exit());
+            // namespaced and other years' solvers share the fixtures of their day
+            let number = advent2021::split_year(&day)
+                .1
+                .rsplit("::")
+                .next()
+                .and_then(|number| number.parse().ok())
+                .unwrap_or_else(|| invalid_day_error.exit());
+            let page = subcommand.value_of("PAGE").expect("page was not provided");
+            return examples::run(number, page.as_ref(), subcommand.is_present("force"))
+                .unwrap_or_else(|e| panic!("{}", e));
+        }
+        Some(("dashboard", subcommand)) => {
+            let mut days: Vec<_> = match subcommand.values_of("DAY") {
+                Some(days) => days.collect(),
+                None => {
+                    let mut days: Vec<_> = advent2021::DAYS
+                        .iter()
+                        .copied()
+                        .chain(config.externals())
+                        .collect();
+                    days.sort_by_key(|day| report::number(day).parse::<u8>().ok());
+                    days
+                }
+            }
+            .into_iter()
+            .map(|day| {
+                config
+                    .get_day(day)
+                    .unwrap_or_else(|_| invalid_day_error.exit())
+            })
+            .collect();
+            // with a namespace configured, a day and its namespaced solver are the same
+            days.dedup_by(|(day, _, _, _), (previous, _, _, _)| day == previous);
+            let live = io::stdout().is_terminal();
+            let answered = dashboard::run(&config, &days, io::stdout().lock(), live)
+                .expect("Unable to write to stdout");
+            if !answered {
+                std::process::exit(1);
+            }
+            return;
+        }
+        Some(("leaderboard", subcommand)) => {
+            if subcommand.value_of("FILE").is_none() && config.leaderboard().is_none() {
+                missing_leaderboard_error.exit();
+            }
+            return leaderboard::run(&config, subcommand.value_of("FILE"), &mut io::stdout())
+                .unwrap_or_else(|e| panic!("{}", e));
+        }
+        Some(("stream", subcommand)) => {
+            let day = subcommand.value_of("DAY").expect("day was not provided");
+            let (day, _, _, _) = config
+                .get_day(day)
+                .unwrap_or_else(|_| invalid_day_error.exit());
+            let (part_one, part_two) =
+                advent2021::get_streaming(&day).unwrap_or_else(|| unstreamable_day_error.exit());
+            let (part, solver) = match subcommand.value_of("PART") {
+                Some("1") => (1, part_one),
+                _ => (2, part_two),
+            };
+            let strict = config.strict_input();
+            let result = match subcommand.value_of("FILE") {
+                None | Some("-") => solver(&mut normalize::lines(
+                    advent2021::stream_lines(io::stdin().lock()),
+                    strict,
+                )),
+                Some(path) => {
+                    let file = File::open(path)
+                        .unwrap_or_else(|e| panic!("Unable to read from {} - {}", path, e));
+                    solver(&mut normalize::lines(
+                        advent2021::stream_lines(BufReader::new(file)),
+                        strict,
+                    ))
+                }
+            };
+            let outcome = result.map_or_else(|e| Outcome::Failed(e.to_string()), Outcome::Answer);
+            let format = subcommand
+                .value_of("format")
+                .map_or(config.format, |format| {
+                    format.parse().expect("clap only accepts known formats")
+                });
+            output::write_part(
+                &mut io::stdout().lock(),
+                format,
+                config.colored(),
+                day.as_str(),
+                None,
+                part,
+                &outcome,
+            )
+            .expect("Unable to write to stdout");
+            if !matches!(outcome, Outcome::Answer(_)) {
+                std::process::exit(1);
+            }
+            return;
+        }
+        _ => subcommand_error.exit(),
     };
 
+    let colored = config.colored();
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    let mut failed = false;
+    let history = config.history();
     days.for_each(|day| {
-        let (day, input_path, part_one, part_two) =
-            advent2021::get_day(day).unwrap_or_else(|_| invalid_day_error.exit());
-
-        let contents = read_to_string(input_path)
-            .unwrap_or_else(|e| panic!("Unable to read from {} - {}", input_path, e));
-
-        println!(
-            "Day {}, Part One: {}",
-            day,
-            part_one(contents.as_str()).unwrap()
-        );
-        println!(
-            "Day {}, Part Two: {}",
-            day,
-            part_two(contents.as_str()).unwrap()
-        );
+        let (day, input_path, part_one, part_two) = config
+            .get_day(day)
+            .unwrap_or_else(|_| invalid_day_error.exit());
+        let solvers = parameterize(day.as_str(), part_one, part_two, overrides.as_slice())
+            .unwrap_or_else(|_| invalid_param_error.exit());
+        let cache = cache.as_ref().filter(|_| !config.is_external(day.as_str()));
+        // overridden parameters are only read by the solvers of a single part
+        let both = advent2021::get_both(day.as_str()).filter(|_| overrides.is_empty());
+
+        let inputs = inputs::select(&input_path, &selection).unwrap_or_else(|e| panic!("{}", e));
+        inputs.iter().for_each(|(input, input_path)| {
+            let contents = advent2021::read_input(input_path)
+                .unwrap_or_else(|e| panic!("Unable to read from {} - {}", input_path.display(), e));
+            // an input rejected in strict mode fails both parts
+            let contents: Result<Arc<str>, String> =
+                normalize::normalize(&contents, config.strict_input())
+                    .map(|contents| contents.into())
+                    .map_err(|e| format!("{} - {}", input_path.display(), e));
+
+            let outcomes = match (&contents, both) {
+                (Ok(contents), Some(both)) => {
+                    let solve = || solve_both(both, contents.clone(), config.timeout());
+                    match cache {
+                        Some(cache) => {
+                            let keys = [1, 2].map(|part| {
+                                cache.key(day.as_str(), part, overrides.as_slice(), contents)
+                            });
+                            cache.get_or_solve_both(keys, solve)
+                        }
+                        None => solve(),
+                    }
+                }
+                _ => solvers
+                    .clone()
+                    .map(|(part, solver)| match (&contents, cache) {
+                        (Ok(contents), Some(cache)) => {
+                            let key = cache.key(day.as_str(), part, overrides.as_slice(), contents);
+                            cache.get_or_solve(key, || {
+                                solve(solver.clone(), contents.clone(), config.timeout())
+                            })
+                        }
+                        (Ok(contents), None) => solve(solver, contents.clone(), config.timeout()),
+                        (Err(e), _) => Outcome::Failed(e.clone()),
+                    }),
+            };
+
+            [1, 2]
+                .into_iter()
+                .zip(outcomes)
+                .for_each(|(part, outcome)| {
+                    failed |= !matches!(outcome, Outcome::Answer(_));
+                    // only answers to the puzzle as given are history, not experiments
+                    if let (Outcome::Answer(answer), None, true) =
+                        (&outcome, input, overrides.is_empty())
+                    {
+                        if let Err(e) = history::record(&history, day.as_str(), part, *answer) {
+                            log::warn!(target: "history", "Unable to record an answer - {}", e);
+                        }
+                    }
+                    if let (Outcome::Invalid(diagnostic), Format::Text) = (&outcome, format) {
+                        eprint!("{}", diagnostic.render(day.as_str()));
+                    }
+                    output::write_part(
+                        &mut stdout,
+                        format,
+                        colored,
+                        day.as_str(),
+                        input.as_deref(),
+                        part,
+                        &outcome,
+                    )
+                    .expect("Unable to write to stdout");
+                });
+        });
     });
+    stdout.flush().expect("Unable to write to stdout");
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+// Days declaring parameters are solved with the overrides applied, other days can't be overridden
+fn parameterize(
+    day: &str,
+    part_one: PartSolver,
+    part_two: PartSolver,
+    overrides: &[(&str, usize)],
+) -> anyhow::Result<[(usize, PartSolver); 2]> {
+    let (parameters, part_one_with, part_two_with) = match advent2021::get_parameterized(day) {
+        Some(parameterized) => parameterized,
+        None if overrides.is_empty() => return Ok([(1, part_one), (2, part_two)]),
+        None => return Err(anyhow::anyhow!("day {} has no parameters", day)),
+    };
+    let params_one = Params::new(parameters, 1, overrides)?;
+    let params_two = Params::new(parameters, 2, overrides)?;
+    Ok([
+        (
+            1,
+            Arc::new(move |contents: &str| part_one_with(contents, &params_one)),
+        ),
+        (
+            2,
+            Arc::new(move |contents: &str| part_two_with(contents, &params_two)),
+        ),
+    ])
+}
+
+fn solve(solver: PartSolver, contents: Arc<str>, timeout: Option<Duration>) -> Outcome {
+    match run(move || solver(&contents), timeout) {
+        Ok(answer) => Outcome::Answer(answer),
+        Err(outcome) => outcome,
+    }
+}
+
+// Both parts from one pass, a failure of which fails both
+fn solve_both(solver: BothSolver, contents: Arc<str>, timeout: Option<Duration>) -> [Outcome; 2] {
+    match run(move || solver(&contents), timeout) {
+        Ok((one, two)) => [Outcome::Answer(one), Outcome::Answer(two)],
+        Err(outcome) => [outcome.clone(), outcome],
+    }
+}
+
+// The answers of a solver, or the outcome reporting why there are none
+fn run<T: Send + 'static>(
+    solve: impl FnOnce() -> anyhow::Result<T> + Send + 'static,
+    timeout: Option<Duration>,
+) -> Result<T, Outcome> {
+    let failed = |e: anyhow::Error| match e.downcast::<Diagnostic>() {
+        Ok(diagnostic) => Outcome::Invalid(diagnostic),
+        Err(e) => Outcome::Failed(e.to_string()),
+    };
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return solve().map_err(failed),
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    // A solver that times out can't be interrupted, it is left to end with the process
+    thread::spawn(move || sender.send(solve()));
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => result.map_err(failed),
+        Err(RecvTimeoutError::Timeout) => Err(Outcome::TimedOut(timeout)),
+        Err(RecvTimeoutError::Disconnected) => Err(Outcome::Failed("solver panicked".to_string())),
+    }
 }
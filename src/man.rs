@@ -0,0 +1,125 @@
+use std::io::{self, Write};
+
+const BIN_NAME: &str = env!("CARGO_BIN_NAME");
+
+pub fn generate(app: &clap::App, out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, ".TH {} 1", BIN_NAME.to_uppercase())?;
+    writeln!(out, ".SH NAME")?;
+    writeln!(out, "{} \\- {}", BIN_NAME, escape(app.get_name()))?;
+    writeln!(out, ".SH SYNOPSIS")?;
+    writeln!(
+        out,
+        "{} \\fISUBCOMMAND\\fR [\\fIARGS\\fR]",
+        synopsis(BIN_NAME, app)
+    )?;
+
+    writeln!(out, ".SH SUBCOMMANDS")?;
+    app.get_subcommands().try_for_each(|subcommand| {
+        writeln!(out, ".TP")?;
+        writeln!(out, "{}", synopsis(subcommand.get_name(), subcommand))?;
+        writeln!(out, "{}", escape(subcommand.get_about().unwrap_or("")))?;
+        crate::arguments(subcommand)
+            .filter(|arg| arg.get_long().is_some() && arg.get_about().is_some())
+            .try_for_each(|arg| {
+                writeln!(out, ".RS")?;
+                writeln!(out, ".TP")?;
+                writeln!(
+                    out,
+                    "\\fB\\-\\-{}\\fR",
+                    escape(arg.get_long().expect("filtered on long"))
+                )?;
+                writeln!(
+                    out,
+                    "{}",
+                    escape(arg.get_about().expect("filtered on about"))
+                )?;
+                writeln!(out, ".RE")
+            })?;
+        subcommand.get_subcommands().try_for_each(|nested| {
+            writeln!(out, ".RS")?;
+            writeln!(out, ".TP")?;
+            writeln!(out, "{}", synopsis(nested.get_name(), nested))?;
+            writeln!(out, "{}", escape(nested.get_about().unwrap_or("")))?;
+            writeln!(out, ".RE")
+        })
+    })?;
+
+    writeln!(out, ".SH DAYS")?;
+    writeln!(
+        out,
+        "Days are selected by number or by word (ie \\fI6\\fR or \\fIsix\\fR), optionally prefixed by \
+         their year (ie \\fI2021:6\\fR). Alternative solvers are namespaced (ie \\fIjhorstmann::6\\fR). \
+         The available days of {} are:",
+        advent2021::DEFAULT_YEAR
+    )?;
+    writeln!(out, ".PP")?;
+    writeln!(out, "{}", escape(advent2021::DAYS.join(", ").as_str()))?;
+
+    writeln!(out, ".SH AUTHOR")?;
+    writeln!(out, "{}", escape(crate::AUTHOR))
+}
+
+fn synopsis(name: &str, app: &clap::App) -> String {
+    let mut synopsis = format!("\\fB{}\\fR", escape(name));
+    crate::arguments(app).for_each(|arg| {
+        let name = escape(arg.get_name());
+        let flag = match (arg.get_long(), arg.get_short()) {
+            (Some(long), _) => Some(format!("\\fB\\-\\-{}\\fR", escape(long))),
+            (None, Some(short)) => Some(format!("\\fB\\-{}\\fR", short)),
+            (None, None) => None,
+        };
+        match flag {
+            Some(flag) if arg.is_set(clap::ArgSettings::TakesValue) => {
+                synopsis.push_str(format!(" [{} \\fI{}\\fR]", flag, name.to_uppercase()).as_str())
+            }
+            Some(flag) => synopsis.push_str(format!(" [{}]", flag).as_str()),
+            None if arg.is_set(clap::ArgSettings::MultipleOccurrences) => {
+                synopsis.push_str(format!(" \\fI{}\\fR...", name).as_str())
+            }
+            None => synopsis.push_str(format!(" \\fI{}\\fR", name).as_str()),
+        }
+    });
+    synopsis
+}
+
+fn escape(text: &str) -> String {
+    let escaped = text.replace('\\', "\\\\").replace('-', "\\-");
+    // a leading control character would be read as a request
+    if escaped.starts_with('.') || escaped.starts_with('\'') {
+        format!("\\&{}", escaped)
+    } else {
+        escaped
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "all-days")]
+    #[test]
+    fn test_generate() {
+        let mut out = Vec::new();
+        generate(&crate::app(), &mut out).unwrap();
+        let page = String::from_utf8(out).unwrap();
+
+        assert!(page.starts_with(
+            ".TH ADVENT2021 1\n.SH NAME\nadvent2021 \\- Advent of Code 2021 Solver\n"
+        ));
+        assert!(page.contains(
+            ".SH SYNOPSIS\n\\fBadvent2021\\fR [\\fB\\-\\-config\\fR \\fICONFIG\\fR] [\\fB\\-\\-year\\fR \\fIYEAR\\fR] [\\fB\\-\\-strict\\-input\\fR] [\\fB\\-v\\fR] [\\fB\\-\\-log\\fR \\fILOG\\fR] \\fISUBCOMMAND\\fR"
+        ));
+        assert!(
+            page.contains(".TP\n\\fBday\\fR \\fIDAY\\fR... [\\fB\\-\\-format\\fR \\fIFORMAT\\fR]")
+        );
+        assert!(page.contains("[\\fB\\-\\-param\\fR \\fIPARAM\\fR]\nSelect day to solve\n"));
+        assert!(page.contains("\\fBserve\\fR [\\fB\\-\\-port\\fR \\fIPORT\\fR]\n"));
+        assert!(page.contains("jhorstmann::6, 7"));
+        assert!(page.contains(".RS\n.TP\n\\fBclear\\fR\nRemove every cached answer\n.RE\n"));
+    }
+
+    #[test]
+    fn test_escape() {
+        assert_eq!("\\&.hidden \\- file", escape(".hidden - file"));
+    }
+}
@@ -0,0 +1,55 @@
+use core::fmt;
+use core::str::FromStr;
+
+use anyhow::{anyhow, Error};
+
+/// Describes the solvers of a day returned by `get_day`, their parameters are looked up with
+/// `get_parameterized`
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Metadata {
+    pub title: &'static str,
+    /// The author's handle, which is also the namespace of alternative solvers
+    pub author: &'static str,
+    pub tags: &'static [Tag],
+    /// Whether the tests check the solvers against the examples of the puzzle
+    pub examples: bool,
+}
+
+/// What a puzzle mostly exercises
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tag {
+    Grid,
+    Graph,
+    Simulation,
+    Parsing,
+}
+
+pub const TAGS: &[Tag] = &[Tag::Grid, Tag::Graph, Tag::Simulation, Tag::Parsing];
+
+impl Tag {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Tag::Grid => "grid",
+            Tag::Graph => "graph",
+            Tag::Simulation => "simulation",
+            Tag::Parsing => "parsing",
+        }
+    }
+}
+
+impl fmt::Display for Tag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Tag {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Error> {
+        TAGS.iter()
+            .find(|tag| tag.name() == s)
+            .copied()
+            .ok_or_else(|| anyhow!("unknown tag '{}'", s))
+    }
+}
@@ -0,0 +1,171 @@
+use alloc::{
+    borrow::Cow,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use anyhow::{anyhow, Result};
+
+const BOM: char = '\u{feff}';
+
+/// Brings an input into the form every solver receives: no byte order mark, LF line endings, no
+/// whitespace at the end of lines and no blank lines or newline at the end of the input. In
+/// strict mode anything beyond the final newline is reported instead of fixed.
+pub fn normalize(contents: &str, strict: bool) -> Result<Cow<'_, str>> {
+    if strict {
+        check(contents)?;
+    }
+    let contents = contents.strip_prefix(BOM).unwrap_or(contents).trim_end();
+    let normalized = !contents.contains('\r')
+        && contents
+            .lines()
+            .all(|line| !line.ends_with(char::is_whitespace));
+    if normalized {
+        Ok(Cow::Borrowed(contents))
+    } else {
+        let lines: Vec<_> = contents.lines().map(str::trim_end).collect();
+        Ok(Cow::Owned(lines.join("\n")))
+    }
+}
+
+fn check(contents: &str) -> Result<()> {
+    if contents.starts_with(BOM) {
+        return Err(anyhow!("input starts with a byte order mark"));
+    }
+    let contents = contents.strip_suffix('\n').unwrap_or(contents);
+    contents
+        .split('\n')
+        .enumerate()
+        .try_for_each(|(i, line)| match line.chars().last() {
+            Some('\r') => Err(anyhow!("line {} ends with CRLF", i + 1)),
+            Some(c) if c.is_whitespace() => Err(anyhow!("line {} ends with whitespace", i + 1)),
+            _ => Ok(()),
+        })?;
+    if contents.ends_with('\n') {
+        return Err(anyhow!("input ends with blank lines"));
+    }
+    Ok(())
+}
+
+/// Normalises lines as they are read, the streaming counterpart of `normalize`. Line endings are
+/// left to the reader, `stream_lines` already drops CRLF.
+pub fn lines<I: Iterator<Item = Result<String>>>(lines: I, strict: bool) -> Lines<I> {
+    Lines {
+        lines,
+        strict,
+        number: 0,
+        blanks: 0,
+        held: None,
+    }
+}
+
+/// Iterator of `lines`
+pub struct Lines<I> {
+    lines: I,
+    strict: bool,
+    number: usize,
+    // blank lines are only passed on once a line follows them
+    blanks: usize,
+    held: Option<String>,
+}
+
+impl<I: Iterator<Item = Result<String>>> Lines<I> {
+    fn fix(&self, line: String) -> Result<String> {
+        let line = match (self.number, line.strip_prefix(BOM)) {
+            (1, Some(_)) if self.strict => {
+                return Err(anyhow!("input starts with a byte order mark"))
+            }
+            (1, Some(stripped)) => stripped.to_string(),
+            _ => line,
+        };
+        match line.trim_end() {
+            trimmed if trimmed.len() == line.len() => Ok(line),
+            _ if self.strict => Err(anyhow!("line {} ends with whitespace", self.number)),
+            trimmed => Ok(trimmed.to_string()),
+        }
+    }
+}
+
+impl<I: Iterator<Item = Result<String>>> Iterator for Lines<I> {
+    type Item = Result<String>;
+
+    fn next(&mut self) -> Option<Result<String>> {
+        if self.held.is_some() {
+            return match self.blanks {
+                0 => self.held.take().map(Ok),
+                _ => {
+                    self.blanks -= 1;
+                    Some(Ok(String::new()))
+                }
+            };
+        }
+        loop {
+            let line = match self.lines.next() {
+                Some(Ok(line)) => line,
+                Some(Err(e)) => return Some(Err(e)),
+                None if self.strict && self.blanks > 0 => {
+                    self.blanks = 0;
+                    return Some(Err(anyhow!("input ends with blank lines")));
+                }
+                None => return None,
+            };
+            self.number += 1;
+            let line = match self.fix(line) {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+            if line.is_empty() {
+                self.blanks += 1;
+            } else {
+                self.held = Some(line);
+                return self.next();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!("3,4,3", normalize("3,4,3\n", false).unwrap());
+        assert_eq!("3,4,3", normalize("3,4,3\n", true).unwrap());
+        assert!(matches!(
+            normalize("1\n\n2\n", false).unwrap(),
+            Cow::Borrowed("1\n\n2")
+        ));
+        assert_eq!(
+            "NN -> C\n\nCH -> B",
+            normalize("\u{feff}NN -> C \r\n\r\nCH -> B\r\n\r\n", false).unwrap()
+        );
+
+        let error = |contents| normalize(contents, true).unwrap_err().to_string();
+        assert_eq!("input starts with a byte order mark", error("\u{feff}1\n"));
+        assert_eq!("line 2 ends with CRLF", error("1\n2\r\n3"));
+        assert_eq!("line 1 ends with whitespace", error("1 \n2\n"));
+        assert_eq!("input ends with blank lines", error("1\n2\n\n"));
+    }
+
+    #[test]
+    fn test_lines() {
+        let normalized = |input: &[&str], strict| {
+            lines(input.iter().map(|line| Ok(line.to_string())), strict)
+                .collect::<Result<Vec<_>>>()
+                .map_err(|e| e.to_string())
+        };
+        assert_eq!(
+            Ok(vec!["1".to_string(), "".to_string(), "2".to_string()]),
+            normalized(&["\u{feff}1 ", "", "2", "", ""], false)
+        );
+        assert_eq!(
+            Err("line 1 ends with whitespace".to_string()),
+            normalized(&["1 ", "2"], true)
+        );
+        assert_eq!(
+            Err("input ends with blank lines".to_string()),
+            normalized(&["1", ""], true)
+        );
+    }
+}
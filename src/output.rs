@@ -0,0 +1,190 @@
+use std::io::{self, Write};
+use std::time::Duration;
+
+use advent2021::config::Format;
+use advent2021::diagnostic::Diagnostic;
+
+pub const BOLD_GREEN: &str = "\x1b[1;32m";
+pub const BOLD_RED: &str = "\x1b[1;31m";
+pub const RESET: &str = "\x1b[0m";
+
+#[derive(Clone)]
+pub enum Outcome {
+    Answer(u64),
+    Failed(String),
+    /// A loader rejected the input, rendered in full on stderr next to the line on stdout
+    Invalid(Diagnostic),
+    TimedOut(Duration),
+}
+
+/// Writes the outcome of one part, as a line of text or a JSON object per line. Named inputs are
+/// labelled, the default input of a day isn't.
+pub fn write_part(
+    out: &mut impl Write,
+    format: Format,
+    colored: bool,
+    day: &str,
+    input: Option<&str>,
+    part: usize,
+    outcome: &Outcome,
+) -> io::Result<()> {
+    match format {
+        Format::Text => {
+            let (color, text) = match outcome {
+                Outcome::Answer(answer) => (BOLD_GREEN, answer.to_string()),
+                Outcome::Failed(message) => (BOLD_RED, format!("failed - {}", message)),
+                Outcome::Invalid(diagnostic) => (BOLD_RED, format!("failed - {}", diagnostic)),
+                Outcome::TimedOut(timeout) => {
+                    (BOLD_RED, format!("timed out after {}s", timeout.as_secs()))
+                }
+            };
+            let part = if part == 1 { "One" } else { "Two" };
+            let day = match input {
+                Some(input) => format!("{} ({})", day, input),
+                None => day.to_string(),
+            };
+            if colored {
+                writeln!(
+                    out,
+                    "Day {}, Part {}: {}{}{}",
+                    day, part, color, text, RESET
+                )
+            } else {
+                writeln!(out, "Day {}, Part {}: {}", day, part, text)
+            }
+        }
+        Format::Json => {
+            let result = match outcome {
+                Outcome::Answer(answer) => format!("\"answer\":{}", answer),
+                Outcome::Failed(message) => format!("\"error\":{}", json_string(message)),
+                Outcome::Invalid(diagnostic) => {
+                    let problem = &diagnostic.problem;
+                    let column = problem
+                        .column
+                        .map_or_else(String::new, |column| format!(",\"column\":{}", column));
+                    format!(
+                        "\"error\":{},\"line\":{}{}",
+                        json_string(&problem.message),
+                        problem.line,
+                        column
+                    )
+                }
+                Outcome::TimedOut(timeout) => format!("\"timeout_secs\":{}", timeout.as_secs()),
+            };
+            let input = match input {
+                Some(input) => format!("\"input\":{},", json_string(input)),
+                None => String::new(),
+            };
+            writeln!(
+                out,
+                "{{\"day\":{},{}\"part\":{},{}}}",
+                json_string(day),
+                input,
+                part,
+                result
+            )
+        }
+    }
+}
+
+pub fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    value.chars().for_each(|c| match c {
+        '"' => escaped.push_str("\\\""),
+        '\\' => escaped.push_str("\\\\"),
+        '\n' => escaped.push_str("\\n"),
+        '\r' => escaped.push_str("\\r"),
+        '\t' => escaped.push_str("\\t"),
+        c if (c as u32) < 0x20 => escaped.push_str(format!("\\u{:04x}", c as u32).as_str()),
+        c => escaped.push(c),
+    });
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use advent2021::lint::Problem;
+
+    use super::*;
+
+    fn line(format: Format, colored: bool, outcome: Outcome) -> String {
+        let mut out = Vec::new();
+        write_part(&mut out, format, colored, "6", None, 2, &outcome).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_write_part() {
+        assert_eq!(
+            "Day 6, Part Two: 26984457539\n",
+            line(Format::Text, false, Outcome::Answer(26984457539))
+        );
+        assert_eq!(
+            "Day 6, Part Two: \x1b[1;31mtimed out after 3s\x1b[0m\n",
+            line(
+                Format::Text,
+                true,
+                Outcome::TimedOut(Duration::from_secs(3))
+            )
+        );
+        assert_eq!(
+            "{\"day\":\"6\",\"part\":2,\"error\":\"bad input\"}\n",
+            line(Format::Json, true, Outcome::Failed("bad input".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_write_invalid() {
+        let diagnostic = || {
+            Diagnostic::new(
+                "load_paper",
+                Problem::new(3, "'x' is not a coordinate".to_string()).at(3),
+                "6,x",
+            )
+        };
+        assert_eq!(
+            "Day 6, Part Two: failed - line 3, column 3: 'x' is not a coordinate\n",
+            line(Format::Text, false, Outcome::Invalid(diagnostic()))
+        );
+        assert_eq!(
+            "{\"day\":\"6\",\"part\":2,\"error\":\"'x' is not a coordinate\",\"line\":3,\"column\":3}\n",
+            line(Format::Json, false, Outcome::Invalid(diagnostic()))
+        );
+    }
+
+    #[test]
+    fn test_write_named_input() {
+        let mut out = Vec::new();
+        write_part(
+            &mut out,
+            Format::Text,
+            false,
+            "5",
+            Some("alice"),
+            1,
+            &Outcome::Answer(5),
+        )
+        .unwrap();
+        write_part(
+            &mut out,
+            Format::Json,
+            false,
+            "5",
+            Some("bob"),
+            1,
+            &Outcome::Answer(12),
+        )
+        .unwrap();
+        assert_eq!(
+            "Day 5 (alice), Part One: 5\n{\"day\":\"5\",\"input\":\"bob\",\"part\":1,\"answer\":12}\n",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!("\"a \\\"b\\\"\\n\"", json_string("a \"b\"\n"));
+    }
+}
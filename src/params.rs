@@ -0,0 +1,122 @@
+use alloc::vec::Vec;
+
+use anyhow::{anyhow, Result};
+
+/// A puzzle constant a solver reads from its `Params`, with the value of each part or `None` when
+/// the part doesn't read it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Parameter {
+    pub name: &'static str,
+    pub about: &'static str,
+    pub part_one: Option<usize>,
+    pub part_two: Option<usize>,
+}
+
+impl Parameter {
+    /// The value `part` (1 or 2) reads by default
+    pub fn default(&self, part: usize) -> Option<usize> {
+        match part {
+            1 => self.part_one,
+            _ => self.part_two,
+        }
+    }
+}
+
+/// The parameter values a part is solved with
+#[derive(Clone, Debug, PartialEq)]
+pub struct Params {
+    values: Vec<(&'static str, usize)>,
+}
+
+impl Params {
+    /// The values `part` reads by default
+    pub fn defaults(parameters: &'static [Parameter], part: usize) -> Params {
+        Params {
+            values: parameters
+                .iter()
+                .filter_map(|parameter| Some((parameter.name, parameter.default(part)?)))
+                .collect(),
+        }
+    }
+
+    /// The defaults of `part` with `overrides` applied. Overriding a parameter the part doesn't
+    /// read is allowed, overriding one that isn't declared is an error.
+    pub fn new(
+        parameters: &'static [Parameter],
+        part: usize,
+        overrides: &[(&str, usize)],
+    ) -> Result<Params> {
+        let mut params = Params::defaults(parameters, part);
+        overrides.iter().try_for_each(|(name, value)| {
+            if !parameters.iter().any(|parameter| parameter.name == *name) {
+                return Err(anyhow!("no parameter '{}'", name));
+            }
+            if let Some((_, current)) = params.values.iter_mut().find(|(n, _)| n == name) {
+                *current = *value;
+            }
+            Ok(())
+        })?;
+        Ok(params)
+    }
+
+    /// The value of a parameter the part reads, panics if the solver didn't declare it
+    pub fn get(&self, name: &str) -> usize {
+        self.values
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, value)| *value)
+            .unwrap_or_else(|| panic!("parameter '{}' is not declared", name))
+    }
+}
+
+/// Parses an override like `days=18`
+pub fn parse_override(s: &str) -> Result<(&str, usize)> {
+    let (name, value) = s
+        .split_once('=')
+        .ok_or_else(|| anyhow!("'{}' is not name=value", s))?;
+    let value = value
+        .parse()
+        .map_err(|_| anyhow!("'{}' is not a value for {}", value, name))?;
+    Ok((name, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMETERS: &[Parameter] = &[
+        Parameter {
+            name: "days",
+            about: "days to simulate",
+            part_one: Some(80),
+            part_two: Some(256),
+        },
+        Parameter {
+            name: "top",
+            about: "largest basins to multiply",
+            part_one: None,
+            part_two: Some(3),
+        },
+    ];
+
+    #[test]
+    fn test_params() {
+        assert_eq!(80, Params::defaults(PARAMETERS, 1).get("days"));
+        assert_eq!(3, Params::defaults(PARAMETERS, 2).get("top"));
+
+        let params = Params::new(PARAMETERS, 1, &[("days", 18), ("top", 2)]).unwrap();
+        assert_eq!(18, params.get("days"));
+        assert_eq!(
+            Params::defaults(PARAMETERS, 1).values.len(),
+            params.values.len()
+        );
+        assert!(Params::new(PARAMETERS, 2, &[("steps", 10)]).is_err());
+    }
+
+    #[test]
+    fn test_parse_override() {
+        assert_eq!(("days", 18), parse_override("days=18").unwrap());
+        assert!(parse_override("days").is_err());
+        assert!(parse_override("days=many").is_err());
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use advent2021::config::{Config, PartSolver};
+use advent2021::diagnostic::Diagnostic;
+use advent2021::normalize::normalize;
+use advent2021::params::{Parameter, Params};
+use anyhow::{anyhow, Result};
+
+const HELP: &str = "\
+day <DAY>           select a day and load its puzzle input
+load <PATH>         replace the input with the contents of a file
+paste               replace the input with lines from stdin, ending with a line containing only '.'
+one | two | run     solve part one, part two or both
+set <NAME> <VALUE>  override a parameter of the selected day (ie `set days 18` for day 6)
+unset <NAME>        restore a parameter to the puzzle value
+params              show the parameters of the selected day
+inspect             show the parsed input of the selected day
+help                show this message
+quit                leave the repl";
+
+struct Day {
+    name: String,
+    part_one: PartSolver,
+    part_two: PartSolver,
+}
+
+#[derive(Default)]
+struct Session {
+    day: Option<Day>,
+    contents: Option<String>,
+    overrides: HashMap<String, usize>,
+    strict: bool,
+}
+
+#[derive(Clone, Copy)]
+enum Part {
+    One,
+    Two,
+}
+
+pub fn run(config: &Config) -> io::Result<()> {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    interact(config, stdin.lock(), stdout.lock())
+}
+
+fn interact(config: &Config, mut input: impl BufRead, mut output: impl Write) -> io::Result<()> {
+    let mut session = Session {
+        strict: config.strict_input(),
+        ..Session::default()
+    };
+    writeln!(output, "Type 'help' for a list of commands")?;
+    loop {
+        write!(output, "advent> ")?;
+        output.flush()?;
+
+        let mut line = String::new();
+        if input.read_line(&mut line)? == 0 {
+            writeln!(output)?;
+            return Ok(());
+        }
+
+        let words: Vec<_> = line.split_whitespace().collect();
+        let result = match words.as_slice() {
+            [] => Ok(()),
+            ["quit"] | ["exit"] => return Ok(()),
+            ["help"] => writeln!(output, "{}", HELP).map_err(anyhow::Error::new),
+            ["day", day] => session.select(config, day),
+            ["load", path] => session.load(path),
+            ["paste"] => session.paste(&mut input),
+            ["one"] => session.solve(Part::One, &mut output),
+            ["two"] => session.solve(Part::Two, &mut output),
+            ["run"] => session
+                .solve(Part::One, &mut output)
+                .and_then(|_| session.solve(Part::Two, &mut output)),
+            ["set", name, value] => session.set(name, value),
+            ["unset", name] => session.unset(name),
+            ["params"] => session.params(&mut output),
+            ["inspect"] => session.inspect(&mut output),
+            _ => Err(anyhow!("unrecognized command, try 'help'")),
+        };
+
+        match result {
+            Ok(()) => {}
+            Err(e) => match (e.downcast_ref::<Diagnostic>(), &session.day) {
+                (Some(diagnostic), Some(day)) => {
+                    write!(output, "{}", diagnostic.render(day.name.as_str()))?
+                }
+                _ => writeln!(output, "error: {}", e)?,
+            },
+        }
+    }
+}
+
+impl Session {
+    fn select(&mut self, config: &Config, day: &str) -> Result<()> {
+        let (name, input_path, part_one, part_two) = config.get_day(day)?;
+        self.day = Some(Day {
+            name,
+            part_one,
+            part_two,
+        });
+        self.overrides.clear();
+        self.load(input_path)
+    }
+
+    fn load(&mut self, path: impl AsRef<Path>) -> Result<()> {
+        let path = path.as_ref();
+        let contents = advent2021::read_input(path)
+            .map_err(|e| anyhow!("Unable to read from {} - {}", path.display(), e))?;
+        self.set_contents(contents.as_str())
+    }
+
+    fn paste(&mut self, input: &mut impl BufRead) -> Result<()> {
+        let mut contents = String::new();
+        loop {
+            let mut line = String::new();
+            if input.read_line(&mut line)? == 0 || line.trim_end() == "." {
+                break;
+            }
+            contents.push_str(line.as_str());
+        }
+        self.set_contents(contents.as_str())
+    }
+
+    fn set_contents(&mut self, contents: &str) -> Result<()> {
+        self.contents = Some(normalize(contents, self.strict)?.into_owned());
+        Ok(())
+    }
+
+    fn solve(&self, part: Part, output: &mut impl Write) -> Result<()> {
+        let day = self.day()?;
+        let contents = self
+            .contents
+            .as_ref()
+            .ok_or_else(|| anyhow!("no input loaded"))?;
+
+        let answer = match advent2021::get_parameterized(day.name.as_str()) {
+            Some((parameters, part_one, part_two)) => {
+                let overrides: Vec<_> = self
+                    .overrides
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), *value))
+                    .collect();
+                let (part, solver) = match part {
+                    Part::One => (1, part_one),
+                    Part::Two => (2, part_two),
+                };
+                solver(contents, &Params::new(parameters, part, &overrides)?)?
+            }
+            None => match part {
+                Part::One => (day.part_one)(contents)?,
+                Part::Two => (day.part_two)(contents)?,
+            },
+        };
+
+        let part = match part {
+            Part::One => "One",
+            Part::Two => "Two",
+        };
+        writeln!(output, "Day {}, Part {}: {}", day.name, part, answer)?;
+        Ok(())
+    }
+
+    fn set(&mut self, name: &str, value: &str) -> Result<()> {
+        let day = self.day()?;
+        match advent2021::get_parameterized(day.name.as_str()) {
+            Some((parameters, _, _)) if parameters.iter().any(|p| p.name == name) => {
+                self.overrides.insert(name.to_string(), value.parse()?);
+                Ok(())
+            }
+            _ => Err(anyhow!("day {} has no parameter '{}'", day.name, name)),
+        }
+    }
+
+    fn unset(&mut self, name: &str) -> Result<()> {
+        self.overrides
+            .remove(name)
+            .map(|_| ())
+            .ok_or_else(|| anyhow!("'{}' was not set", name))
+    }
+
+    fn params(&self, output: &mut impl Write) -> Result<()> {
+        let day = self.day()?;
+        let parameters = match advent2021::get_parameterized(day.name.as_str()) {
+            Some((parameters, _, _)) => parameters,
+            None => {
+                writeln!(output, "day {} has no parameters", day.name)?;
+                return Ok(());
+            }
+        };
+        parameters.iter().try_for_each(|parameter| {
+            let value = match (self.overrides.get(parameter.name), parameter) {
+                (Some(value), _) => format!("{} (set)", value),
+                (
+                    None,
+                    Parameter {
+                        part_one: Some(one),
+                        part_two: Some(two),
+                        ..
+                    },
+                ) if one == two => {
+                    format!("{} (both parts)", one)
+                }
+                (
+                    None,
+                    Parameter {
+                        part_one, part_two, ..
+                    },
+                ) => part_one
+                    .map(|one| format!("{} (part one)", one))
+                    .into_iter()
+                    .chain(part_two.map(|two| format!("{} (part two)", two)))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            };
+            writeln!(
+                output,
+                "{} = {} - {}",
+                parameter.name, value, parameter.about
+            )
+        })?;
+        Ok(())
+    }
+
+    fn inspect(&self, output: &mut impl Write) -> Result<()> {
+        let day = self.day()?;
+        let inspector = advent2021::get_inspector(day.name.as_str())
+            .ok_or_else(|| anyhow!("day {} can't be inspected", day.name))?;
+        let contents = self
+            .contents
+            .as_ref()
+            .ok_or_else(|| anyhow!("no input loaded"))?;
+        write!(output, "{}", inspector(contents)?)?;
+        Ok(())
+    }
+
+    fn day(&self) -> Result<&Day> {
+        self.day
+            .as_ref()
+            .ok_or_else(|| anyhow!("no day selected, try 'day <DAY>'"))
+    }
+}
+
+// every session selects a day, day 6 at least
+#[cfg(all(test, feature = "day6"))]
+mod tests {
+    use super::*;
+
+    fn transcript(commands: &str) -> String {
+        let mut output = Vec::new();
+        interact(&Config::default(), commands.as_bytes(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_paste_and_solve() {
+        let output = transcript("day 6\npaste\n3,4,3,1,2\n.\nset days 18\none\nunset days\none\n");
+        assert!(output.contains("Day 6, Part One: 26\n"));
+        assert!(output.contains("Day 6, Part One: 5934\n"));
+    }
+
+    #[cfg(feature = "all-days")]
+    mod all_days {
+        use super::*;
+
+        #[test]
+        fn test_params() {
+            let output = transcript("day 9\nparams\nday 14\nset steps 5\nparams\n");
+            assert!(output.contains("basins = 3 (part two) - largest basins to multiply\n"));
+            assert!(output.contains("steps = 5 (set) - pair insertion steps to apply\n"));
+        }
+
+        #[test]
+        fn test_errors_keep_session() {
+            let output = transcript("one\nday 7\nset days 18\nnonsense\n");
+            assert!(output.contains("error: no day selected, try 'day <DAY>'\n"));
+            assert!(output.contains("error: day 7 has no parameter 'days'\n"));
+            assert!(output.contains("error: unrecognized command, try 'help'\n"));
+        }
+
+        #[test]
+        fn test_diagnostics() {
+            let output = transcript("day 13\npaste\n6,x\n.\none\n");
+            assert!(
+                output.contains("error: 'x' is not a coordinate\n --> day 13, load_paper, line 1")
+            );
+            assert!(output.contains("1 | 6,x\n  |   ^\n"));
+        }
+    }
+}
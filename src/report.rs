@@ -0,0 +1,353 @@
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fs::read_to_string;
+use std::io::{self, Write};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use advent2021::config::{Config, PartSolver};
+use advent2021::normalize::normalize;
+
+use crate::inputs::answers_path;
+use crate::output::Outcome;
+
+thread_local! {
+    // Allocations of the thread since it started counting, none when it isn't
+    static ALLOCATIONS: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// The system allocator, counting the allocations of a thread while it measures a solver so
+/// reports can show how many the solver makes. Other threads, ie solvers left running after
+/// timing out, aren't counted.
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn count() {
+        // unavailable while the thread is being torn down, when nothing is measured
+        let _ = ALLOCATIONS.try_with(|allocations| {
+            if let Some(count) = allocations.get() {
+                allocations.set(Some(count + 1));
+            }
+        });
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        CountingAllocator::count();
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        CountingAllocator::count();
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+// The allocations `f` makes on the calling thread
+fn count_allocations(f: impl FnOnce()) -> usize {
+    ALLOCATIONS.with(|allocations| allocations.set(Some(0)));
+    f();
+    ALLOCATIONS.with(|allocations| allocations.take().unwrap_or(0))
+}
+
+struct Row {
+    day: String,
+    title: &'static str,
+    author: String,
+    part: usize,
+    outcome: Outcome,
+    expected: Option<u64>,
+    median: Option<Duration>,
+    allocations: Option<usize>,
+}
+
+/// Solves every day of `DEFAULT_YEAR` and writes a table of the answers, whether they match the
+/// answers file, the median time of `runs` solves and the allocations of one. Alternative and
+/// external solvers are listed next to the day they solve.
+pub fn run(config: &Config, runs: usize, html: bool, out: &mut impl Write) -> io::Result<()> {
+    let mut days: Vec<_> = advent2021::DAYS
+        .iter()
+        .copied()
+        .chain(config.externals())
+        .collect();
+    days.sort_by_key(|day| number(day).parse::<u8>().ok());
+    let rows: Vec<_> = days
+        .iter()
+        .flat_map(|day| solve_day(config, day, runs))
+        .collect();
+    if html {
+        write_html(rows.as_slice(), out)
+    } else {
+        write_markdown(rows.as_slice(), out)
+    }
+}
+
+// The day of a namespaced solver, ie `6` of `alice::6`
+pub fn number(day: &str) -> &str {
+    day.rsplit("::").next().unwrap_or(day)
+}
+
+fn solve_day(config: &Config, day: &str, runs: usize) -> Vec<Row> {
+    let metadata = advent2021::get_metadata(day).or_else(|| advent2021::get_metadata(number(day)));
+    let title = metadata.map_or("-", |metadata| metadata.title);
+    let author = match day.split_once("::") {
+        Some((namespace, _)) => namespace,
+        None => metadata.map_or("-", |metadata| metadata.author),
+    };
+    let row = |part, outcome| Row {
+        day: day.to_string(),
+        title,
+        author: author.to_string(),
+        part,
+        outcome,
+        expected: None,
+        median: None,
+        allocations: None,
+    };
+
+    // external solvers are looked up through the config, which would prefer its namespace for
+    // the solvers of the library
+    let day_parts = if config.is_external(day) {
+        config.get_day(day)
+    } else {
+        let (_, input_path, part_one, part_two) =
+            advent2021::get_day(day).expect("DAYS are solvable");
+        Ok((
+            day.to_string(),
+            config.input_path(input_path),
+            Arc::new(part_one) as PartSolver,
+            Arc::new(part_two) as PartSolver,
+        ))
+    };
+    let (_, input_path, part_one, part_two) = match day_parts {
+        Ok(day_parts) => day_parts,
+        Err(e) => {
+            return (1..=2)
+                .map(|part| row(part, Outcome::Failed(e.to_string())))
+                .collect()
+        }
+    };
+    let expected = read_to_string(answers_path(&input_path)).unwrap_or_default();
+    let mut expected = expected.lines().map(|answer| answer.trim().parse().ok());
+    let contents = advent2021::read_input(&input_path)
+        .map_err(|e| format!("Unable to read from {} - {}", input_path.display(), e))
+        .and_then(|contents| {
+            normalize(&contents, config.strict_input())
+                .map(|contents| Arc::<str>::from(contents.as_ref()))
+                .map_err(|e| format!("{} - {}", input_path.display(), e))
+        });
+
+    [(1, part_one), (2, part_two)]
+        .into_iter()
+        .map(|(part, solver)| {
+            log::info!(target: "report", "solving day {}, part {}", day, part);
+            let outcome = match &contents {
+                Ok(contents) => crate::solve(solver.clone(), contents.clone(), config.timeout()),
+                Err(e) => Outcome::Failed(e.clone()),
+            };
+            // only solvers known to finish are measured
+            let (median, allocations) = match (&outcome, &contents) {
+                (Outcome::Answer(_), Ok(contents)) => {
+                    let (median, allocations) = measure(solver.as_ref(), contents, runs);
+                    (Some(median), Some(allocations))
+                }
+                _ => (None, None),
+            };
+            Row {
+                expected: expected.next().flatten(),
+                median,
+                allocations,
+                ..row(part, outcome)
+            }
+        })
+        .collect()
+}
+
+fn measure(
+    solver: &(dyn Fn(&str) -> anyhow::Result<u64> + Send + Sync),
+    contents: &str,
+    runs: usize,
+) -> (Duration, usize) {
+    let allocations = count_allocations(|| {
+        let _ = solver(contents);
+    });
+
+    let mut durations: Vec<_> = (0..runs.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            let _ = solver(contents);
+            start.elapsed()
+        })
+        .collect();
+    durations.sort_unstable();
+    (durations[durations.len() / 2], allocations)
+}
+
+impl Row {
+    fn cells(&self) -> [String; 8] {
+        let answer = match &self.outcome {
+            Outcome::Answer(answer) => answer.to_string(),
+            Outcome::Failed(message) => format!("failed - {}", message),
+            Outcome::Invalid(diagnostic) => format!("failed - {}", diagnostic),
+            Outcome::TimedOut(timeout) => format!("timed out after {}s", timeout.as_secs()),
+        };
+        let verified = match (&self.outcome, self.expected) {
+            (Outcome::Answer(answer), Some(expected)) if *answer == expected => "✓".to_string(),
+            (_, Some(expected)) => format!("✗ expected {}", expected),
+            (_, None) => "-".to_string(),
+        };
+        [
+            self.day.clone(),
+            self.title.to_string(),
+            self.author.clone(),
+            if self.part == 1 { "One" } else { "Two" }.to_string(),
+            answer,
+            verified,
+            self.median
+                .map_or_else(|| "-".to_string(), |median| format!("{:.2?}", median)),
+            self.allocations
+                .map_or_else(|| "-".to_string(), |allocations| allocations.to_string()),
+        ]
+    }
+}
+
+const HEADERS: [&str; 8] = [
+    "Day",
+    "Puzzle",
+    "Author",
+    "Part",
+    "Answer",
+    "Verified",
+    "Median",
+    "Allocations",
+];
+
+fn write_markdown(rows: &[Row], out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "| {} |", HEADERS.join(" | "))?;
+    writeln!(out, "|{}", "---|".repeat(HEADERS.len()))?;
+    rows.iter().try_for_each(|row| {
+        // pipes would end the cell early
+        let cells = row.cells().map(|cell| cell.replace('|', "\\|"));
+        writeln!(out, "| {} |", cells.join(" | "))
+    })
+}
+
+fn write_html(rows: &[Row], out: &mut impl Write) -> io::Result<()> {
+    writeln!(out, "<!DOCTYPE html>")?;
+    writeln!(out, "<html>")?;
+    writeln!(
+        out,
+        "<head><meta charset=\"utf-8\"><title>Advent of Code {}</title></head>",
+        advent2021::DEFAULT_YEAR
+    )?;
+    writeln!(out, "<body>")?;
+    writeln!(out, "<table>")?;
+    let headers: Vec<_> = HEADERS
+        .iter()
+        .map(|header| format!("<th>{}</th>", header))
+        .collect();
+    writeln!(out, "<tr>{}</tr>", headers.concat())?;
+    rows.iter().try_for_each(|row| {
+        let cells: Vec<_> = row
+            .cells()
+            .iter()
+            .map(|cell| format!("<td>{}</td>", escape_html(cell)))
+            .collect();
+        writeln!(out, "<tr>{}</tr>", cells.concat())
+    })?;
+    writeln!(out, "</table>")?;
+    writeln!(out, "</body>")?;
+    writeln!(out, "</html>")
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows() -> Vec<Row> {
+        vec![
+            Row {
+                day: "6".to_string(),
+                title: "Lanternfish",
+                author: "medwards".to_string(),
+                part: 1,
+                outcome: Outcome::Answer(5934),
+                expected: Some(5934),
+                median: Some(Duration::from_micros(1500)),
+                allocations: Some(3),
+            },
+            Row {
+                day: "jhorstmann::6".to_string(),
+                title: "Lanternfish",
+                author: "jhorstmann".to_string(),
+                part: 2,
+                outcome: Outcome::Failed("a|b <c>".to_string()),
+                expected: Some(26984457539),
+                median: None,
+                allocations: None,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_count_allocations() {
+        // an allocation and a reallocation
+        let allocations = count_allocations(|| {
+            let mut numbers = vec![1];
+            numbers.push(2);
+            assert_eq!(3, numbers.iter().sum::<i32>());
+        });
+        assert_eq!(2, allocations);
+
+        // another thread allocating meanwhile isn't counted
+        let barrier = Arc::new(std::sync::Barrier::new(2));
+        let other = barrier.clone();
+        let worker = std::thread::spawn(move || {
+            other.wait();
+            let allocated: Vec<_> = (0..100).map(|n| vec![n; 8]).collect();
+            other.wait();
+            allocated.len()
+        });
+        let allocations = count_allocations(|| {
+            barrier.wait();
+            barrier.wait();
+        });
+        assert_eq!(0, allocations);
+        assert_eq!(100, worker.join().unwrap());
+    }
+
+    #[test]
+    fn test_write_markdown() {
+        let mut out = Vec::new();
+        write_markdown(rows().as_slice(), &mut out).unwrap();
+        assert_eq!(
+            "\
+| Day | Puzzle | Author | Part | Answer | Verified | Median | Allocations |
+|---|---|---|---|---|---|---|---|
+| 6 | Lanternfish | medwards | One | 5934 | ✓ | 1.50ms | 3 |
+| jhorstmann::6 | Lanternfish | jhorstmann | Two | failed - a\\|b <c> | ✗ expected 26984457539 | - | - |
+",
+            String::from_utf8(out).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_write_html() {
+        let mut out = Vec::new();
+        write_html(rows().as_slice(), &mut out).unwrap();
+        let page = String::from_utf8(out).unwrap();
+        assert!(page.contains("<tr><th>Day</th><th>Puzzle</th>"));
+        assert!(page.contains("<td>failed - a|b &lt;c&gt;</td>"));
+    }
+}
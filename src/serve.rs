@@ -0,0 +1,252 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::panic::catch_unwind;
+use std::thread;
+use std::time::Instant;
+
+use advent2021::normalize::normalize;
+
+use crate::output::json_string;
+
+// Inputs larger than this are refused rather than buffered
+const MAX_BODY_LEN: usize = 64 * 1024 * 1024;
+
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn json(status: u16, body: String) -> Self {
+        Response { status, body }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        Response::json(status, format!("{{\"error\":{}}}", json_string(message)))
+    }
+
+    fn reason(&self) -> &'static str {
+        match self.status {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            405 => "Method Not Allowed",
+            413 => "Payload Too Large",
+            _ => "Internal Server Error",
+        }
+    }
+}
+
+/// Serves the solvers on localhost until the process is killed
+pub fn run(port: u16, strict: bool) -> io::Result<()> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+    println!("Listening on http://{}", listener.local_addr()?);
+    for stream in listener.incoming() {
+        let stream = stream?;
+        thread::spawn(move || {
+            if let Err(e) = handle_connection(stream, strict) {
+                eprintln!("Connection failed - {}", e);
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(mut stream: TcpStream, strict: bool) -> io::Result<()> {
+    let response = match read_request(&mut BufReader::new(&stream))? {
+        Ok(request) => route(
+            request.method.as_str(),
+            request.path.as_str(),
+            request.body.as_slice(),
+            strict,
+        ),
+        Err(response) => response,
+    };
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.reason(),
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+// Reads the request line, headers and body. Malformed requests produce the response to send.
+fn read_request(reader: &mut impl BufRead) -> io::Result<Result<Request, Response>> {
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (method, path) = match (parts.next(), parts.next()) {
+        (Some(method), Some(path)) => (method.to_string(), path.to_string()),
+        _ => return Ok(Err(Response::error(400, "malformed request line"))),
+    };
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = match value.trim().parse() {
+                    Ok(length) => length,
+                    Err(_) => return Ok(Err(Response::error(400, "invalid Content-Length"))),
+                };
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_LEN {
+        return Ok(Err(Response::error(413, "input is too large")));
+    }
+    let mut body = vec![0; content_length];
+    reader.read_exact(body.as_mut_slice())?;
+    Ok(Ok(Request { method, path, body }))
+}
+
+fn route(method: &str, path: &str, body: &[u8], strict: bool) -> Response {
+    let path = percent_decode(path.split('?').next().unwrap_or(""));
+    let segments: Vec<_> = path.trim_matches('/').split('/').collect();
+    match (method, segments.as_slice()) {
+        ("GET", ["days"]) => list_days(),
+        ("POST", ["days", day, "parts", part]) => solve(day, part, body, strict),
+        (_, ["days"]) | (_, ["days", _, "parts", _]) => Response::error(405, "method not allowed"),
+        _ => Response::error(404, "no such route"),
+    }
+}
+
+fn list_days() -> Response {
+    let days: Vec<_> = advent2021::DAYS
+        .iter()
+        .map(|day| json_string(day))
+        .collect();
+    Response::json(200, format!("{{\"days\":[{}]}}", days.join(",")))
+}
+
+fn solve(day: &str, part: &str, body: &[u8], strict: bool) -> Response {
+    let (day, _, part_one, part_two) = match advent2021::get_day(day) {
+        Ok(day_parts) => day_parts,
+        Err(_) => return Response::error(404, "no such day"),
+    };
+    let (part, solver) = match part {
+        "1" | "one" => (1, part_one),
+        "2" | "two" => (2, part_two),
+        _ => return Response::error(404, "no such part"),
+    };
+    let contents = match std::str::from_utf8(body) {
+        Ok(contents) => contents,
+        Err(_) => return Response::error(400, "input is not valid UTF-8"),
+    };
+    let contents = match normalize(contents, strict) {
+        Ok(contents) => contents,
+        Err(e) => return Response::error(400, e.to_string().as_str()),
+    };
+
+    let start = Instant::now();
+    let answer = catch_unwind(|| solver(&contents));
+    let elapsed = start.elapsed();
+    match answer {
+        Ok(Ok(answer)) => Response::json(
+            200,
+            format!(
+                "{{\"day\":{},\"part\":{},\"answer\":{},\"elapsed_micros\":{}}}",
+                json_string(day),
+                part,
+                answer,
+                elapsed.as_micros()
+            ),
+        ),
+        Ok(Err(e)) => Response::error(400, e.to_string().as_str()),
+        Err(_) => Response::error(500, "solver panicked"),
+    }
+}
+
+// Only needed for namespaced days, ie `jhorstmann%3A%3A6`
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 3)
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (bytes[i], escaped) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(decoded.as_slice()).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "all-days")]
+    #[test]
+    fn test_route() {
+        let response = route(
+            "POST",
+            "/days/one/parts/1",
+            b"199\n200\n208\n210\n200\n207\n240\n269\n260\n263\n",
+            false,
+        );
+        assert_eq!(200, response.status);
+        assert!(response
+            .body
+            .starts_with("{\"day\":\"1\",\"part\":1,\"answer\":7,"));
+        assert_eq!(404, route("POST", "/days/26/parts/1", b"", false).status);
+        assert_eq!(404, route("POST", "/days/1/parts/3", b"", false).status);
+        assert_eq!(405, route("GET", "/days/1/parts/1", b"", false).status);
+        assert_eq!(400, route("POST", "/days/1/parts/1", b"x", false).status);
+        assert_eq!(404, route("GET", "/", b"", false).status);
+        assert_eq!(
+            400,
+            route("POST", "/days/6/parts/1", b"3,4 \r\n", true).status
+        );
+        assert!(route("GET", "/days", b"", false)
+            .body
+            .contains("\"jhorstmann::6\""));
+    }
+
+    #[test]
+    fn test_read_request() {
+        let mut request: &[u8] =
+            b"POST /days/6/parts/1 HTTP/1.1\r\nHost: localhost\r\ncontent-length: 9\r\n\r\n3,4,3,1,2";
+        assert_eq!(
+            Request {
+                method: "POST".to_string(),
+                path: "/days/6/parts/1".to_string(),
+                body: b"3,4,3,1,2".to_vec()
+            },
+            read_request(&mut request).unwrap().unwrap()
+        );
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(
+            "/days/jhorstmann::6",
+            percent_decode("/days/jhorstmann%3A%3a6")
+        );
+        assert_eq!("100%", percent_decode("100%"));
+    }
+}